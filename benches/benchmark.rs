@@ -28,33 +28,158 @@ fn serialize_1_benchmark(c: &mut Criterion) {
 
 fn serialize_5_benchmark(c: &mut Criterion) {
     c.bench_function("ser 5", |b| {
-        b.iter(|| serialize_varint(u32::max_value()))
+        b.iter(|| serialize_varint(u32::MAX))
     });
     c.bench_function("ser 5 (128)", |b| {
-        b.iter(|| serialize_varint_128(u32::max_value() as u128))
+        b.iter(|| serialize_varint_128(u32::MAX as u128))
     });
 }
 
 fn serialize_9_benchmark(c: &mut Criterion) {
     c.bench_function("ser 9", |b| {
-        b.iter(|| serialize_varint(u64::max_value()))
+        b.iter(|| serialize_varint(u64::MAX))
     });
     c.bench_function("ser 9 (128)", |b| {
-        b.iter(|| serialize_varint_128(u64::max_value() as u128))
+        b.iter(|| serialize_varint_128(u64::MAX as u128))
     });
 }
 
 fn serialize_17_benchmark(c: &mut Criterion) {
     c.bench_function("ser 17", |b| {
-        b.iter(|| serialize_varint_128(u128::max_value()))
+        b.iter(|| serialize_varint_128(u128::MAX))
     });
 }
 
+fn serialize_widths_benchmark(c: &mut Criterion) {
+    for v in [25u128, 1000, 50_000, 1 << 20, 1 << 30, 1 << 38, 1 << 46, 1 << 54, 1 << 62, 1 << 100] {
+        c.bench_function(&format!("ser width {} (128)", v.varint_size()), |b| {
+            b.iter(|| serialize_varint(v))
+        });
+    }
+}
+
+fn encoded(v: u128) -> Vec<u8> {
+    let mut buf = Vec::new();
+    buf.write_varint(v).unwrap();
+    buf
+}
+
+fn deserialize_benchmark(c: &mut Criterion) {
+    for v in [25u128, u32::MAX as u128, u64::MAX as u128, u128::MAX] {
+        let buf = encoded(v);
+        let len = buf.len();
+        c.bench_function(&format!("de {} (read_varint 128)", len), |b| {
+            b.iter(|| {
+                let mut r = criterion::black_box(&buf[..]);
+                ReadVarint::<u128>::read_varint(&mut r).unwrap()
+            })
+        });
+        c.bench_function(&format!("de {} (deserialize 128)", len), |b| {
+            b.iter(|| {
+                let mut r = criterion::black_box(&buf[..]);
+                Varint::<u128>::deserialize(&mut r).unwrap()
+            })
+        });
+        c.bench_function(&format!("de {} (slice 128)", len), |b| {
+            b.iter(|| read_varint_slice::<u128>(criterion::black_box(&buf)).unwrap())
+        });
+    }
+}
+
+fn deserialize_mixed_benchmark(c: &mut Criterion) {
+    let values: Vec<u128> = (0..4096u32).map(|i| 1u128 << (i * 7 % 128)).collect();
+    let mut buf = Vec::new();
+    for v in &values {
+        buf.write_varint(*v).unwrap();
+    }
+    c.bench_function("de mixed (read_varint 128)", |b| {
+        b.iter(|| {
+            let mut r = criterion::black_box(&buf[..]);
+            let mut sum = 0u128;
+            for _ in 0..values.len() {
+                let v: u128 = r.read_varint().unwrap();
+                sum = sum.wrapping_add(v);
+            }
+            sum
+        })
+    });
+}
+
+fn encode_column(values: impl Iterator<Item = u64>) -> Vec<u8> {
+    let mut buf = Vec::new();
+    for v in values {
+        buf.write_varint(v).unwrap();
+    }
+    buf
+}
+
+fn decode_one_at_a_time(mut buf: &[u8], out: &mut [u64]) -> usize {
+    let mut count = 0;
+    while !buf.is_empty() && count < out.len() {
+        let (v, len) = read_varint_slice::<u64>(buf).unwrap();
+        out[count] = v;
+        count += 1;
+        buf = &buf[len..];
+    }
+    count
+}
+
+fn decode_many_benchmark(c: &mut Criterion) {
+    let columns = [
+        ("small", encode_column((0..65536u64).map(|i| i % 200))),
+        ("mixed", encode_column((0..65536u64).map(|i| if i % 10 == 0 { i * 1000 } else { i % 200 }))),
+        ("large", encode_column((0..65536u64).map(|i| i << 20))),
+    ];
+    let mut out = vec![0u64; 65536];
+    for (name, buf) in columns.iter() {
+        c.bench_function(&format!("decode loop {}", name), |b| {
+            b.iter(|| decode_one_at_a_time(criterion::black_box(buf), &mut out))
+        });
+        c.bench_function(&format!("decode_many {}", name), |b| {
+            b.iter(|| decode_many(criterion::black_box(buf), &mut out).unwrap())
+        });
+    }
+}
+
+fn encode_paths_benchmark(c: &mut Criterion) {
+    let columns: [(&str, Vec<u64>); 3] = [
+        ("2 bytes", (0..4096u64).map(|i| 241 + i % 1791).collect()),
+        ("3 bytes", (0..4096u64).map(|i| 2032 + i * 16).collect()),
+        ("mixed", (0..4096u64).map(|i| 1 << (i * 7 % 64)).collect()),
+    ];
+    let mut out = vec![0u8; 4096 * 9];
+    for (name, values) in columns.iter() {
+        c.bench_function(&format!("encode {} (Write)", name), |b| {
+            b.iter(|| {
+                let mut w = &mut out[..];
+                for v in criterion::black_box(values) {
+                    w.write_varint(*v).unwrap();
+                }
+                w.len()
+            })
+        });
+        c.bench_function(&format!("encode {} (slice)", name), |b| {
+            b.iter(|| {
+                let mut pos = 0;
+                for v in criterion::black_box(values) {
+                    pos += write_varint_slice(&mut out[pos..], *v).unwrap();
+                }
+                pos
+            })
+        });
+    }
+}
+
 criterion_group!(
     benches,
     serialize_1_benchmark,
     serialize_5_benchmark,
     serialize_9_benchmark,
-    serialize_17_benchmark
+    serialize_17_benchmark,
+    serialize_widths_benchmark,
+    deserialize_benchmark,
+    deserialize_mixed_benchmark,
+    decode_many_benchmark,
+    encode_paths_benchmark
 );
 criterion_main!(benches);
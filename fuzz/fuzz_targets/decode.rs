@@ -0,0 +1,30 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use varuint::{read_varint_slice, Deserializable, ReadVarint, Varint, VarintIter};
+
+// Arbitrary bytes must decode to a value or an error, never panic.
+fuzz_target!(|data: &[u8]| {
+    let mut r = data;
+    let io: std::io::Result<u128> = r.read_varint();
+    let slice = read_varint_slice::<u128>(data);
+    match (io, slice) {
+        (Ok(a), Ok((b, len))) => {
+            assert_eq!(a, b);
+            assert_eq!(data.len() - len, r.len());
+        }
+        (Err(_), Err(_)) => {}
+        (io, slice) => panic!("io {:?} and slice {:?} disagree", io, slice),
+    }
+
+    let _ = Varint::<u128>::deserialize(&mut &data[..]);
+    let _ = Varint::<i64>::deserialize(&mut &data[..]);
+    let _ = Varint::<i8>::deserialize(&mut &data[..]);
+    let _ = ReadVarint::<u16>::try_read_varint(&mut &data[..]);
+    let _ = ReadVarint::<u32>::read_varint_canonical(&mut &data[..]);
+    for v in VarintIter::<u64>::new(data) {
+        if v.is_err() {
+            break;
+        }
+    }
+});
@@ -0,0 +1,41 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use varuint::{
+    read_varint_slice, Deserializable, ReadVarint, Serializable, Varint, VarintSizeHint,
+    WriteVarint,
+};
+
+fn roundtrip<T>(v: T)
+where
+    T: Copy + PartialEq + std::fmt::Debug + VarintSizeHint + varuint::VarintBaseType,
+    Vec<u8>: WriteVarint<T>,
+    for<'a> &'a [u8]: ReadVarint<T>,
+    Varint<T>: Serializable + Deserializable,
+{
+    let mut buf = Vec::new();
+    let len = buf.write_varint(v).unwrap();
+    assert_eq!(len, buf.len());
+    assert_eq!(len, v.varint_size());
+    assert_eq!(v, (&buf[..]).read_varint().unwrap());
+    assert_eq!(Ok((v, len)), read_varint_slice::<T>(&buf));
+
+    let mut ser = Vec::new();
+    Varint(v).serialize(&mut ser).unwrap();
+    assert_eq!(buf, ser);
+    assert_eq!(Varint(v), Varint::deserialize(&mut &ser[..]).unwrap());
+}
+
+// Any value must encode and decode back to itself.
+fuzz_target!(|input: (u128, i128, u64, i64, u32, i32, u16, i16, u8, i8)| {
+    roundtrip(input.0);
+    roundtrip(input.1);
+    roundtrip(input.2);
+    roundtrip(input.3);
+    roundtrip(input.4);
+    roundtrip(input.5);
+    roundtrip(input.6);
+    roundtrip(input.7);
+    roundtrip(input.8);
+    roundtrip(input.9);
+});
@@ -0,0 +1,78 @@
+//! Async support over tokio I/O traits, enabled by the `tokio` feature.
+use crate::read_write::varint_len_from_first_byte;
+use crate::{ReadVarint, WriteVarint};
+use std::io::{Error, ErrorKind, Result};
+use tokio::io::{
+    AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt,
+};
+
+/// Result of peeking at buffered data
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum VarintPeek<T> {
+    /// A complete value is buffered, `len` bytes must be consumed to skip it
+    Ready { value: T, len: usize },
+    /// The buffer holds only a prefix of the encoding
+    Incomplete,
+}
+
+/// Decode a value from the buffered data without consuming it.
+///
+/// Returns `VarintPeek::Incomplete` if the whole encoding is not buffered yet,
+/// fails with `UnexpectedEof` at the end of input.
+///
+/// Note that `fill_buf` of `tokio::io::BufReader` refills only an empty buffer, so a value
+/// straddling the end of its buffer stays `Incomplete` on every retry. Use `read_varint_async`
+/// for such readers, `peek_varint` suits `AsyncBufRead` implementations that append incoming
+/// data to the unconsumed bytes.
+pub async fn peek_varint<T, R>(r: &mut R) -> Result<VarintPeek<T>>
+where
+    R: AsyncBufRead + Unpin + ?Sized,
+    for<'a> &'a [u8]: ReadVarint<T>,
+{
+    let buf = r.fill_buf().await?;
+    let first = match buf.first() {
+        Some(b) => *b,
+        None => return Err(Error::from(ErrorKind::UnexpectedEof)),
+    };
+    let len = varint_len_from_first_byte(first);
+    if buf.len() < len {
+        return Ok(VarintPeek::Incomplete);
+    }
+    let value = (&buf[..len]).read_varint()?;
+    Ok(VarintPeek::Ready { value, len })
+}
+
+/// Read a value, the encoding may arrive split across any number of reads.
+///
+/// Returns the same values and errors as `ReadVarint::read_varint`.
+pub async fn read_varint_async<T, R>(r: &mut R) -> Result<T>
+where
+    R: AsyncRead + Unpin + ?Sized,
+    for<'a> &'a [u8]: ReadVarint<T>,
+{
+    let mut buf = [0u8; 17];
+    r.read_exact(&mut buf[..1]).await?;
+    let len = varint_len_from_first_byte(buf[0]);
+    if len > 1 {
+        // reject a leading byte invalid for `T` without waiting for the rest
+        if let Err(e) = ReadVarint::<T>::read_varint(&mut &buf[..1]) {
+            if e.kind() != ErrorKind::UnexpectedEof {
+                return Err(e);
+            }
+        }
+        r.read_exact(&mut buf[1..len]).await?;
+    }
+    (&buf[..len]).read_varint()
+}
+
+/// Write a value, returns bytes written
+pub async fn write_varint_async<T, W>(w: &mut W, v: T) -> Result<usize>
+where
+    W: AsyncWrite + Unpin + ?Sized,
+    for<'a> &'a mut [u8]: WriteVarint<T>,
+{
+    let mut buf = [0u8; 17];
+    let len = (&mut buf[..]).write_varint(v)?;
+    w.write_all(&buf[..len]).await?;
+    Ok(len)
+}
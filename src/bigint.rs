@@ -0,0 +1,63 @@
+//! Arbitrary precision extension of the encoding.
+//!
+//! Values up to `u128::MAX` are encoded exactly as `Varint<u128>` (1-17 bytes).
+//! Larger values are written as the marker `241, 0`, followed by the payload byte length encoded
+//! as `Varint<u64>`, followed by the big-endian payload bytes.
+//!
+//! The marker is the 2-byte form of `240`, which is never written by `write_varint`
+//! (`240` takes a single byte) nor by `write_varint_min_width` (it never pads to 2 bytes).
+//! This is not a visible incompatibility: readers without the `bigint` feature accept the marker
+//! as a non-canonical `240` and then decode the length and payload as further values, so they get
+//! silently corrupted data. Only canonical readers such as `read_varint_canonical` reject it.
+//!
+//! Conversely, a padded `240` from writers outside this crate is read as a marker, such streams
+//! are out of scope.
+use crate::{ReadVarint, WriteVarint};
+use num_bigint::BigUint;
+use std::io::{Error, ErrorKind, Read, Result, Write};
+
+const BIG_MARKER: [u8; 2] = [241, 0];
+
+pub trait WriteVarintBig {
+    fn write_varint_big(&mut self, v: &BigUint) -> Result<usize>;
+}
+
+impl<T: Write + ?Sized> WriteVarintBig for T {
+    fn write_varint_big(&mut self, v: &BigUint) -> Result<usize> {
+        if let Ok(v) = u128::try_from(v) {
+            return self.write_varint(v);
+        }
+        let bytes = v.to_bytes_be();
+        self.write_all(&BIG_MARKER)?;
+        let size = self.write_varint(bytes.len() as u64)?;
+        self.write_all(&bytes)?;
+        Ok(BIG_MARKER.len() + size + bytes.len())
+    }
+}
+
+pub trait ReadVarintBig {
+    fn read_varint_big(&mut self) -> Result<BigUint>;
+}
+
+impl<T: Read + ?Sized> ReadVarintBig for T {
+    fn read_varint_big(&mut self) -> Result<BigUint> {
+        let mut head = [0u8; 2];
+        self.read_exact(&mut head[..1])?;
+        let mut head_len = 1;
+        if head[0] == BIG_MARKER[0] {
+            self.read_exact(&mut head[1..])?;
+            head_len = 2;
+        }
+        if head != BIG_MARKER {
+            let v: u128 = (&head[..head_len]).chain(&mut *self).read_varint()?;
+            return Ok(BigUint::from(v));
+        }
+        let len: u64 = self.read_varint()?;
+        let mut bytes = Vec::new();
+        self.take(len).read_to_end(&mut bytes)?;
+        if bytes.len() as u64 != len {
+            return Err(Error::from(ErrorKind::UnexpectedEof));
+        }
+        Ok(BigUint::from_bytes_be(&bytes))
+    }
+}
@@ -0,0 +1,61 @@
+use std::io::{Error, ErrorKind, Read, Result};
+
+/// Reader enforcing a total byte budget over everything read through it.
+///
+/// Pass `&mut BudgetReader` down to nested decoders so the whole decode tree shares one budget.
+/// Reads fail with `InvalidData` once the budget is exhausted.
+#[derive(Debug)]
+pub struct BudgetReader<R> {
+    inner: R,
+    budget: usize,
+    used: usize,
+}
+
+impl<R: Read> BudgetReader<R> {
+    pub fn new(inner: R, budget: usize) -> Self {
+        Self {
+            inner,
+            budget,
+            used: 0,
+        }
+    }
+
+    /// Bytes consumed so far
+    pub fn used(&self) -> usize {
+        self.used
+    }
+
+    /// Bytes left in the budget
+    pub fn remaining(&self) -> usize {
+        self.budget - self.used
+    }
+
+    /// Read exactly `len` bytes, charged against the budget before allocating
+    pub fn read_bytes(&mut self, len: usize) -> Result<Vec<u8>> {
+        if len > self.remaining() {
+            return Err(Error::from(ErrorKind::InvalidData));
+        }
+        let mut buf = vec![0u8; len];
+        self.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for BudgetReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let limit = buf.len().min(self.remaining());
+        if limit == 0 {
+            return Err(Error::from(ErrorKind::InvalidData));
+        }
+        let n = self.inner.read(&mut buf[..limit])?;
+        self.used += n;
+        Ok(n)
+    }
+}
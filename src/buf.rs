@@ -0,0 +1,47 @@
+//! Extension traits over `bytes` buffers, enabled by the `bytes` feature.
+use crate::read_write::varint_len_from_first_byte;
+use crate::{VarintCodec, VarintError};
+use bytes::{Buf, BufMut};
+
+/// Varint decoding for `bytes::Buf`, including chained and other non-contiguous buffers
+pub trait VarintBuf: Buf {
+    /// Decode a value and advance past it.
+    ///
+    /// Fails without advancing if the leading byte implies a form too wide for `T`
+    /// or fewer bytes remain than the value needs.
+    fn get_varint<T: VarintCodec>(&mut self) -> Result<T, VarintError> {
+        if !self.has_remaining() {
+            return Err(VarintError::UnexpectedEof);
+        }
+        let first = self.chunk()[0];
+        let len = varint_len_from_first_byte(first);
+        if self.remaining() < len {
+            // a too wide leading byte takes precedence over missing bytes
+            let err = T::decode_from_slice(&[first]).err();
+            return Err(err.unwrap_or(VarintError::UnexpectedEof));
+        }
+        if self.chunk().len() >= len {
+            let (v, _) = T::decode_from_slice(&self.chunk()[..len])?;
+            self.advance(len);
+            return Ok(v);
+        }
+        let mut buf = [0u8; 17];
+        self.copy_to_slice(&mut buf[..len]);
+        T::decode_from_slice(&buf[..len]).map(|(v, _)| v)
+    }
+}
+
+impl<B: Buf + ?Sized> VarintBuf for B {}
+
+/// Varint encoding for `bytes::BufMut`
+pub trait VarintBufMut: BufMut {
+    /// Encode a value with `BufMut::put_slice`, returns bytes written
+    fn put_varint<T: VarintCodec + Copy>(&mut self, v: T) -> usize {
+        let mut buf = [0u8; 17];
+        let len = v.encode_to_slice(&mut buf);
+        self.put_slice(&buf[..len]);
+        len
+    }
+}
+
+impl<B: BufMut + ?Sized> VarintBufMut for B {}
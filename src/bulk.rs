@@ -0,0 +1,119 @@
+//! Bulk decoding of varint arrays.
+//!
+//! Runs of single-byte values are detected a block at a time and copied without per-value
+//! branching, other values take the regular slice decoder. Blocks are 8 bytes checked with
+//! word-wide arithmetic, or 16 bytes checked with SSE2 when the `simd` feature is enabled
+//! on `x86_64`.
+use crate::{VarintCodec, VarintError};
+use core::convert::TryInto;
+
+const HIGH_BITS: u64 = 0x8080_8080_8080_8080;
+const LOW_BITS: u64 = 0x7f7f_7f7f_7f7f_7f7f;
+
+/// Number of leading bytes of `block` (in little-endian order) that are single-byte values
+#[inline(always)]
+fn single_byte_prefix(block: u64) -> usize {
+    // for a byte with the high bit set, adding 0x0f to its low 7 bits carries into bit 7
+    // exactly when the byte is >= 241, the sums never carry across bytes
+    let multi = ((block & LOW_BITS) + 0x0f0f_0f0f_0f0f_0f0f) & block & HIGH_BITS;
+    multi.trailing_zeros() as usize / 8
+}
+
+// whole blocks are copied to keep the loop branch-free, slots past the single-byte prefix
+// are overwritten by the following values
+#[inline(always)]
+fn copy_block(src: &[u8], dst: &mut [u64]) {
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d = u64::from(*s);
+    }
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+#[inline(always)]
+fn copy_single_byte_run(buf: &[u8], out: &mut [u64]) -> usize {
+    use core::arch::x86_64::{
+        _mm_cmpeq_epi8, _mm_loadu_si128, _mm_max_epu8, _mm_movemask_epi8, _mm_set1_epi8,
+    };
+
+    let mut copied = 0;
+    while buf.len() - copied >= 16 && out.len() - copied >= 16 {
+        let src = &buf[copied..copied + 16];
+        // SAFETY: SSE2 is part of the x86_64 baseline and `src` holds 16 readable bytes
+        let single = unsafe {
+            let v = _mm_loadu_si128(src.as_ptr().cast());
+            let limit = _mm_set1_epi8(240u8 as i8);
+            _mm_movemask_epi8(_mm_cmpeq_epi8(_mm_max_epu8(v, limit), limit)) as u32
+        };
+        let n = (!single | 0x1_0000).trailing_zeros() as usize;
+        copy_block(src, &mut out[copied..copied + 16]);
+        copied += n;
+        if n < 16 {
+            return copied;
+        }
+    }
+    copied + copy_single_byte_run_swar(&buf[copied..], &mut out[copied..])
+}
+
+#[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+#[inline(always)]
+fn copy_single_byte_run(buf: &[u8], out: &mut [u64]) -> usize {
+    copy_single_byte_run_swar(buf, out)
+}
+
+#[inline(always)]
+fn copy_single_byte_run_swar(buf: &[u8], out: &mut [u64]) -> usize {
+    let mut copied = 0;
+    while buf.len() - copied >= 8 && out.len() - copied >= 8 {
+        let src = &buf[copied..copied + 8];
+        let n = single_byte_prefix(u64::from_le_bytes(src.try_into().unwrap()));
+        copy_block(src, &mut out[copied..copied + 8]);
+        copied += n;
+        if n < 8 {
+            break;
+        }
+    }
+    copied
+}
+
+/// Decode values from `buf` until `out` is full or `buf` ends,
+/// returns the number of values decoded and the number of bytes consumed.
+///
+/// Fails with `UnexpectedEof` if `buf` ends mid-value, or with `Overflow` if a leading byte
+/// implies a form wider than `u64` (`255`), no other error is returned. Values decoded before
+/// the failing one are left in `out`. Slots of `out` past the decoded values may be overwritten.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::decode_many;
+///
+/// let buf = [1u8, 2, 241, 1, 3];
+/// let mut out = [0u64; 8];
+/// assert_eq!(Ok((4, 5)), decode_many(&buf, &mut out));
+/// assert_eq!([1, 2, 241, 3], out[..4]);
+/// ```
+pub fn decode_many(buf: &[u8], out: &mut [u64]) -> Result<(usize, usize), VarintError> {
+    let mut pos = 0;
+    let mut count = 0;
+    while count < out.len() && pos < buf.len() {
+        let first = buf[pos];
+        if first > 240 {
+            let (v, len) = u64::decode_from_slice(&buf[pos..])?;
+            out[count] = v;
+            count += 1;
+            pos += len;
+            continue;
+        }
+        let run = match copy_single_byte_run(&buf[pos..], &mut out[count..]) {
+            // a short tail of the input or the output is copied one value at a time
+            0 => {
+                out[count] = u64::from(first);
+                1
+            }
+            run => run,
+        };
+        pos += run;
+        count += run;
+    }
+    Ok((count, pos))
+}
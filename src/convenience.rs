@@ -0,0 +1,59 @@
+//! Shortcuts for encoding to and decoding from owned buffers.
+use crate::{VarintCodec, VarintError, MAX_VARINT_LEN};
+
+/// Encode a single value into a new `Vec`.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::{from_varint_slice, to_varint_vec};
+///
+/// let bytes = to_varint_vec(300u64);
+/// assert_eq!(vec![241, 60], bytes);
+/// assert_eq!(Ok(300u64), from_varint_slice(&bytes));
+/// ```
+pub fn to_varint_vec<T: VarintCodec>(v: T) -> Vec<u8> {
+    let mut buf = [0u8; MAX_VARINT_LEN];
+    let len = v.encode_to_slice(&mut buf);
+    buf[..len].to_vec()
+}
+
+/// Decode a buffer holding exactly one value.
+/// Fails with `TrailingBytes` if anything follows the value.
+pub fn from_varint_slice<T: VarintCodec>(buf: &[u8]) -> Result<T, VarintError> {
+    let (v, len) = T::decode_from_slice(buf)?;
+    if len != buf.len() {
+        return Err(VarintError::TrailingBytes);
+    }
+    Ok(v)
+}
+
+/// Re-encode concatenated values from `src` in their minimal forms, appending them to `dst`.
+/// Returns how many bytes shorter the output is than `src`.
+///
+/// Fails with `UnexpectedEof` if the last value is truncated,
+/// values before it are already appended to `dst`.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::recode_canonical;
+///
+/// // 5 padded to the 9-byte form, then a canonical 241
+/// let src = [254u8, 5, 0, 0, 0, 0, 0, 0, 0, 241, 1];
+/// let mut dst = Vec::new();
+/// assert_eq!(Ok(8), recode_canonical(&src, &mut dst));
+/// assert_eq!(vec![5, 241, 1], dst);
+/// ```
+pub fn recode_canonical(src: &[u8], dst: &mut Vec<u8>) -> Result<usize, VarintError> {
+    let start = dst.len();
+    let mut rest = src;
+    let mut buf = [0u8; MAX_VARINT_LEN];
+    while !rest.is_empty() {
+        let (v, len) = u128::decode_from_slice(rest)?;
+        let size = v.encode_to_slice(&mut buf);
+        dst.extend_from_slice(&buf[..size]);
+        rest = &rest[len..];
+    }
+    Ok(src.len() - (dst.len() - start))
+}
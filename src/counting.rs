@@ -0,0 +1,52 @@
+use std::io::{Result, Write};
+
+/// Writer that discards the data and only counts the bytes.
+///
+/// Sizes a batch through the same `write_varint`/`serialize` code that encodes it,
+/// so the computed size can't drift from the actual encoding.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::{CountingWriter, WriteVarint};
+///
+/// let mut w = CountingWriter::new();
+/// for v in [1u64, 300, 70_000] {
+///     w.write_varint(v).unwrap();
+/// }
+/// assert_eq!(1 + 2 + 4, w.count());
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CountingWriter {
+    count: usize,
+}
+
+impl CountingWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bytes written so far
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl Write for CountingWriter {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.count += buf.len();
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        self.count += buf.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
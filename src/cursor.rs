@@ -0,0 +1,109 @@
+//! Cursors over borrowed byte slices, available without `std`.
+//!
+//! `read_varint`/`write_varint` work on `core` only, with `std` the cursors also implement
+//! `std::io::Read`/`Write`.
+use crate::{VarintCodec, VarintError};
+#[cfg(feature = "std")]
+use std::io::{Read, Result, Write};
+
+/// Reading cursor over a borrowed byte slice
+#[derive(Debug, Clone)]
+pub struct SliceCursor<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> SliceCursor<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Bytes consumed so far
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Bytes left to read
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    /// Decode the next value, the position is not advanced on failure
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use varuint::SliceCursor;
+    ///
+    /// let mut r = SliceCursor::new(&[241, 1, 7]);
+    /// assert_eq!(Ok(241u32), r.read_varint());
+    /// assert_eq!(Ok(7u8), r.read_varint());
+    /// assert_eq!(3, r.position());
+    /// ```
+    pub fn read_varint<T: VarintCodec>(&mut self) -> core::result::Result<T, VarintError> {
+        let (v, len) = T::decode_from_slice(&self.buf[self.pos..])?;
+        self.pos += len;
+        Ok(v)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Read for SliceCursor<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = buf.len().min(self.remaining());
+        buf[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+/// Writing cursor over a borrowed mutable byte slice
+#[derive(Debug)]
+pub struct SliceCursorMut<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> SliceCursorMut<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Bytes written so far
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Free space left
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    /// Encode `v` at the current position, returns the number of bytes written.
+    /// Fails with `BufferTooSmall` without writing anything if the value doesn't fit.
+    pub fn write_varint<T: VarintCodec + Copy>(
+        &mut self,
+        v: T,
+    ) -> core::result::Result<usize, VarintError> {
+        if self.remaining() < v.varint_size() {
+            return Err(VarintError::BufferTooSmall);
+        }
+        let len = v.encode_to_slice(&mut self.buf[self.pos..]);
+        self.pos += len;
+        Ok(len)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Write for SliceCursorMut<'_> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let n = buf.len().min(self.remaining());
+        self.buf[self.pos..self.pos + n].copy_from_slice(&buf[..n]);
+        self.pos += n;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+use crate::read_write::varint_len_from_first_byte;
+use crate::{ReadVarint, VarintCodec, VarintError};
+use std::io::{ErrorKind, Read, Result};
+use std::marker::PhantomData;
+
+/// Resumable varint decoder fed one byte at a time.
+///
+/// Useful with non-blocking sources where a value may be split across several reads.
+/// The decoder resets itself after each value, so a single instance can decode a whole stream.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::VarintDecoder;
+///
+/// let mut decoder = VarintDecoder::<u32>::new();
+/// assert!(decoder.push(249).is_none());
+/// assert!(decoder.push(0xf0).is_none());
+/// assert!(decoder.push(0x07).is_none());
+/// assert_eq!(67568, decoder.push(0x01).unwrap().unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct VarintDecoder<T> {
+    buf: [u8; 17],
+    len: usize,
+    needed: usize,
+    unread: Vec<u8>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: VarintCodec> VarintDecoder<T>
+where
+    for<'a> &'a [u8]: ReadVarint<T>,
+{
+    pub fn new() -> Self {
+        Self {
+            buf: [0u8; 17],
+            len: 0,
+            needed: 0,
+            unread: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Drop any partially received value
+    pub fn reset(&mut self) {
+        self.len = 0;
+        self.needed = 0;
+    }
+
+    /// Whether a partially received value is buffered
+    pub fn is_pending(&self) -> bool {
+        self.len != 0
+    }
+
+    /// Number of bytes still needed to complete the pending value, 0 if nothing is pending
+    pub fn remaining(&self) -> usize {
+        self.needed - self.len
+    }
+
+    /// Feed the next byte, returns `None` while more bytes are needed.
+    /// The decoder is reset after returning a value or an error.
+    /// A leading byte of a form wider than `T` fails with `Overflow` right away.
+    pub fn push(&mut self, byte: u8) -> Option<Result<T>> {
+        if self.len == 0 {
+            self.needed = varint_len_from_first_byte(byte);
+            if self.needed > T::MAX_ENCODED_LEN {
+                self.needed = 0;
+                return Some(Err(VarintError::Overflow.into()));
+            }
+        }
+        self.buf[self.len] = byte;
+        self.len += 1;
+        if self.len < self.needed {
+            return None;
+        }
+        let res = (&self.buf[..self.len]).read_varint();
+        self.reset();
+        Some(res)
+    }
+
+    /// Decode values from `r` into `out` until it is exhausted, returns the number of values decoded.
+    ///
+    /// A value split at the end of input stays buffered and is completed by the next call.
+    /// `WouldBlock` from the reader ends the call the same way as the end of input.
+    /// On a decoding error, bytes already read past the failing value stay buffered
+    /// and are decoded first by the next call.
+    pub fn decode_all<R: Read + ?Sized>(&mut self, r: &mut R, out: &mut Vec<T>) -> Result<usize> {
+        let mut chunk = [0u8; 256];
+        let mut count = 0;
+        let unread = std::mem::take(&mut self.unread);
+        self.feed(&unread, out, &mut count)?;
+        loop {
+            let n = match r.read(&mut chunk) {
+                Ok(0) => return Ok(count),
+                Ok(n) => n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return Ok(count),
+                Err(e) => return Err(e),
+            };
+            self.feed(&chunk[..n], out, &mut count)?;
+        }
+    }
+
+    /// Push `bytes` collecting values into `out`, keeps the bytes after a failing value
+    fn feed(&mut self, bytes: &[u8], out: &mut Vec<T>, count: &mut usize) -> Result<()> {
+        for (i, b) in bytes.iter().enumerate() {
+            match self.push(*b) {
+                Some(Ok(v)) => {
+                    out.push(v);
+                    *count += 1;
+                }
+                Some(Err(e)) => {
+                    self.unread.extend_from_slice(&bytes[i + 1..]);
+                    return Err(e);
+                }
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: VarintCodec> Default for VarintDecoder<T>
+where
+    for<'a> &'a [u8]: ReadVarint<T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
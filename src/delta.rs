@@ -0,0 +1,91 @@
+//! Delta encoding helpers for sequences of integers.
+//!
+//! The sequence length is not written, readers get it from the caller.
+use crate::{ReadVarint, WriteVarint};
+use std::io::{Error, ErrorKind, Read, Result, Write};
+
+/// Write the first value followed by signed (zigzag) deltas between neighbours, returns bytes written.
+/// Fails with `InvalidInput` if a delta does not fit `i64`.
+pub fn write_zigzag_deltas<W: Write + ?Sized>(w: &mut W, values: &[i64]) -> Result<usize> {
+    let mut size = 0;
+    let mut prev = 0i64;
+    for (i, &v) in values.iter().enumerate() {
+        let delta = if i == 0 {
+            v
+        } else {
+            v.checked_sub(prev)
+                .ok_or_else(|| Error::from(ErrorKind::InvalidInput))?
+        };
+        size += w.write_varint(delta)?;
+        prev = v;
+    }
+    Ok(size)
+}
+
+/// Read `n` values written by `write_zigzag_deltas`.
+/// Fails with `InvalidData` if a reconstructed value overflows `i64`.
+pub fn read_zigzag_deltas<R: Read + ?Sized>(r: &mut R, n: usize) -> Result<Vec<i64>> {
+    let mut values = Vec::with_capacity(n.min(1024));
+    let mut prev = 0i64;
+    for i in 0..n {
+        let delta: i64 = r.read_varint()?;
+        prev = if i == 0 {
+            delta
+        } else {
+            prev.checked_add(delta)
+                .ok_or_else(|| Error::from(ErrorKind::InvalidData))?
+        };
+        values.push(prev);
+    }
+    Ok(values)
+}
+
+/// Write the first value followed by the differences between neighbours as zigzag varints,
+/// returns bytes written.
+///
+/// Differences are signed, so decreasing sequences still encode compactly.
+/// Fails with `InvalidInput` if a difference does not fit `i64`.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::{read_varint_deltas, write_varint_deltas};
+///
+/// let timestamps = [1_700_000_000_000u64, 1_700_000_000_250, 1_700_000_000_240];
+/// let mut buf = Vec::new();
+/// assert_eq!(10, write_varint_deltas(&mut buf, &timestamps).unwrap());
+/// assert_eq!(timestamps[..], read_varint_deltas(&mut &buf[..], 3).unwrap()[..]);
+/// ```
+pub fn write_varint_deltas<W: Write + ?Sized>(w: &mut W, values: &[u64]) -> Result<usize> {
+    let mut size = 0;
+    let mut prev = 0u64;
+    for (i, &v) in values.iter().enumerate() {
+        size += if i == 0 {
+            w.write_varint(v)?
+        } else {
+            let delta = i64::try_from(i128::from(v) - i128::from(prev))
+                .map_err(|_| Error::from(ErrorKind::InvalidInput))?;
+            w.write_varint(delta)?
+        };
+        prev = v;
+    }
+    Ok(size)
+}
+
+/// Read `n` values written by `write_varint_deltas`.
+/// Fails with `InvalidData` if a reconstructed value overflows `u64`.
+pub fn read_varint_deltas<R: Read + ?Sized>(r: &mut R, n: usize) -> Result<Vec<u64>> {
+    let mut values = Vec::with_capacity(n.min(1024));
+    let mut prev = 0u64;
+    for i in 0..n {
+        prev = if i == 0 {
+            r.read_varint()?
+        } else {
+            let delta: i64 = r.read_varint()?;
+            prev.checked_add_signed(delta)
+                .ok_or_else(|| Error::from(ErrorKind::InvalidData))?
+        };
+        values.push(prev);
+    }
+    Ok(values)
+}
@@ -0,0 +1,75 @@
+//! Best-effort detection of the encoding scheme of an unknown buffer.
+use crate::leb128::leb128_len;
+use crate::{read_varint_ordered, VarintCodec, VarintSizeHint};
+
+/// Encoding schemes told apart by `detect_scheme`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    /// Default encoding of this crate, as written by `write_varint`
+    Varint,
+    /// Order-preserving encoding, as written by `write_varint_ordered`
+    Ordered,
+    /// LEB128, as written by `write_uleb128`/`write_sleb128`
+    Leb128,
+}
+
+/// Length of the canonical default encoding at the beginning of `buf`
+fn varint_len(buf: &[u8]) -> Option<usize> {
+    let (v, len) = u128::decode_from_slice(buf).ok()?;
+    (v.varint_size() == len).then_some(len)
+}
+
+/// Length of the canonical order-preserving encoding at the beginning of `buf`
+fn ordered_len(buf: &[u8]) -> Option<usize> {
+    let (v, len) = read_varint_ordered::<u128>(buf).ok()?;
+    (v.varint_size() == len).then_some(len)
+}
+
+/// Whether the whole of `buf` splits into values accepted by `value_len`
+fn splits_into(mut buf: &[u8], value_len: fn(&[u8]) -> Option<usize>) -> bool {
+    while !buf.is_empty() {
+        match value_len(buf) {
+            Some(len) => buf = &buf[len..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Guess the scheme of a buffer holding one or more concatenated values.
+///
+/// The guess is heuristic: the buffer is split into values using the header classifier of each
+/// scheme, and a scheme is returned only if it is the single one accepting the whole buffer.
+/// Default and order-preserving values must use their minimal form, they share the header
+/// layout and differ only in the payload byte order of the 4..17-byte forms.
+/// Returns `None` for empty and ambiguous buffers (e.g. values below 128 are valid in every
+/// scheme) and for buffers no scheme accepts.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::{detect_scheme, Scheme};
+///
+/// // 131_072 followed by 368, the payload is minimal only when read in one byte order
+/// assert_eq!(Some(Scheme::Varint), detect_scheme(&[249, 0x00, 0x00, 0x02, 241, 0x80]));
+/// assert_eq!(Some(Scheme::Ordered), detect_scheme(&[249, 0x02, 0x00, 0x00, 241, 0x80]));
+/// // 255 in LEB128, the header 255 would need 16 more bytes
+/// assert_eq!(Some(Scheme::Leb128), detect_scheme(&[0xff, 0x01]));
+/// assert_eq!(None, detect_scheme(&[1, 2, 3]));
+/// ```
+pub fn detect_scheme(buf: &[u8]) -> Option<Scheme> {
+    let mut found = None;
+    for (scheme, value_len) in [
+        (Scheme::Varint, varint_len as fn(&[u8]) -> Option<usize>),
+        (Scheme::Ordered, ordered_len),
+        (Scheme::Leb128, leb128_len),
+    ] {
+        if splits_into(buf, value_len) {
+            if found.is_some() {
+                return None;
+            }
+            found = Some(scheme);
+        }
+    }
+    found
+}
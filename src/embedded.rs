@@ -0,0 +1,79 @@
+//! Support for the `embedded-io` traits, enabled by the `embedded-io` feature. Works without `std`.
+use crate::read_write::varint_len_from_first_byte;
+use crate::{VarintCodec, VarintError, MAX_VARINT_LEN};
+use core::fmt;
+use embedded_io::{Read, ReadExactError, Write};
+
+/// Error of `read_varint_embedded`
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum EmbeddedReadError<E> {
+    /// Input is not a valid varint for the target type or ends mid-value
+    Varint(VarintError),
+    /// Underlying reader failed
+    Io(E),
+}
+
+impl<E: fmt::Debug> fmt::Display for EmbeddedReadError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EmbeddedReadError::Varint(e) => write!(f, "{}", e),
+            EmbeddedReadError::Io(e) => write!(f, "varint read failed: {:?}", e),
+        }
+    }
+}
+
+impl<E> From<VarintError> for EmbeddedReadError<E> {
+    fn from(e: VarintError) -> Self {
+        EmbeddedReadError::Varint(e)
+    }
+}
+
+impl<E> From<ReadExactError<E>> for EmbeddedReadError<E> {
+    fn from(e: ReadExactError<E>) -> Self {
+        match e {
+            ReadExactError::UnexpectedEof => EmbeddedReadError::Varint(VarintError::UnexpectedEof),
+            ReadExactError::Other(e) => EmbeddedReadError::Io(e),
+        }
+    }
+}
+
+/// Read a value from an `embedded_io::Read`.
+///
+/// The end of input mid-value is reported as `VarintError::UnexpectedEof`, a leading byte
+/// implying a form too wide for `T` is rejected with `Overflow` before reading the rest.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::{read_varint_embedded, write_varint_embedded};
+///
+/// let mut buf = [0u8; 17];
+/// let len = write_varint_embedded(&mut &mut buf[..], 67568u32).unwrap();
+/// assert_eq!(67568u32, read_varint_embedded(&mut &buf[..len]).unwrap());
+/// ```
+pub fn read_varint_embedded<T, R>(r: &mut R) -> Result<T, EmbeddedReadError<R::Error>>
+where
+    T: VarintCodec,
+    R: Read + ?Sized,
+{
+    let mut buf = [0u8; MAX_VARINT_LEN];
+    r.read_exact(&mut buf[..1])?;
+    let len = varint_len_from_first_byte(buf[0]);
+    if len > T::MAX_ENCODED_LEN {
+        return Err(VarintError::Overflow.into());
+    }
+    r.read_exact(&mut buf[1..len])?;
+    Ok(T::decode_from_slice(&buf[..len])?.0)
+}
+
+/// Write a value to an `embedded_io::Write`, returns bytes written
+pub fn write_varint_embedded<T, W>(w: &mut W, v: T) -> Result<usize, W::Error>
+where
+    T: VarintCodec + Copy,
+    W: Write + ?Sized,
+{
+    let mut buf = [0u8; MAX_VARINT_LEN];
+    let len = v.encode_to_slice(&mut buf);
+    w.write_all(&buf[..len])?;
+    Ok(len)
+}
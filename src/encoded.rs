@@ -0,0 +1,86 @@
+use crate::{VarintCodec, MAX_VARINT_LEN};
+use core::fmt;
+use core::ops::Deref;
+
+/// Encoded bytes of a single value, stored inline.
+///
+/// Dereferences to the encoded bytes, so it can be passed wherever `&[u8]` is expected
+/// without a heap allocation or a scratch buffer.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::Varint;
+///
+/// let bytes = Varint(300u32).encode();
+/// assert_eq!(bytes, [241, 60]);
+/// assert_eq!(2, bytes.len());
+/// ```
+#[derive(Copy, Clone)]
+pub struct VarintBytes {
+    buf: [u8; MAX_VARINT_LEN],
+    len: u8,
+}
+
+impl VarintBytes {
+    /// Encode `v`
+    #[inline]
+    pub fn new<T: VarintCodec>(v: T) -> Self {
+        let mut buf = [0u8; MAX_VARINT_LEN];
+        let len = v.encode_to_slice(&mut buf);
+        Self {
+            buf,
+            len: len as u8,
+        }
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf[..usize::from(self.len)]
+    }
+}
+
+impl Deref for VarintBytes {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for VarintBytes {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl fmt::Debug for VarintBytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl PartialEq for VarintBytes {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for VarintBytes {}
+
+impl PartialEq<[u8]> for VarintBytes {
+    #[inline]
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<const N: usize> PartialEq<[u8; N]> for VarintBytes {
+    #[inline]
+    fn eq(&self, other: &[u8; N]) -> bool {
+        self.as_slice() == other
+    }
+}
@@ -0,0 +1,71 @@
+use core::fmt;
+
+/// Decoding error with the exact failure reason
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+#[non_exhaustive]
+pub enum VarintError {
+    /// Input ends before the value is complete
+    UnexpectedEof,
+    /// Leading byte is outside the set accepted by a strict or length-limited read
+    InvalidLeadingByte(u8),
+    /// Decoded value does not fit the target type,
+    /// or the leading byte implies a form wider than the target type ever uses
+    Overflow,
+    /// Value is not encoded in the minimal number of bytes
+    NonCanonical,
+    /// Output buffer is shorter than the encoded value
+    BufferTooSmall,
+    /// Input continues after the value where a single value was expected
+    TrailingBytes,
+    /// Underlying reader failed with an error other than the end of input
+    #[cfg(feature = "std")]
+    Io(std::io::ErrorKind),
+}
+
+impl fmt::Display for VarintError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VarintError::UnexpectedEof => write!(f, "unexpected end of varint"),
+            VarintError::InvalidLeadingByte(b) => write!(f, "invalid varint leading byte {}", b),
+            VarintError::Overflow => write!(f, "varint value overflows target type"),
+            VarintError::NonCanonical => write!(f, "non-canonical varint encoding"),
+            VarintError::BufferTooSmall => write!(f, "buffer too small for varint"),
+            VarintError::TrailingBytes => write!(f, "trailing bytes after varint"),
+            #[cfg(feature = "std")]
+            VarintError::Io(kind) => write!(f, "varint read failed: {}", kind),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VarintError {}
+
+#[cfg(feature = "std")]
+impl From<VarintError> for std::io::Error {
+    fn from(e: VarintError) -> Self {
+        use std::io::ErrorKind;
+        let kind = match e {
+            VarintError::UnexpectedEof => ErrorKind::UnexpectedEof,
+            VarintError::InvalidLeadingByte(_)
+            | VarintError::Overflow
+            | VarintError::NonCanonical
+            | VarintError::TrailingBytes => ErrorKind::InvalidData,
+            VarintError::BufferTooSmall => ErrorKind::WriteZero,
+            VarintError::Io(kind) => kind,
+        };
+        std::io::Error::new(kind, e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for VarintError {
+    fn from(e: std::io::Error) -> Self {
+        if let Some(e) = e.get_ref().and_then(|e| e.downcast_ref::<VarintError>()) {
+            return *e;
+        }
+        match e.kind() {
+            std::io::ErrorKind::UnexpectedEof => VarintError::UnexpectedEof,
+            kind => VarintError::Io(kind),
+        }
+    }
+}
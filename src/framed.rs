@@ -0,0 +1,86 @@
+use crate::{read_length_delimited, write_length_delimited};
+use std::io::{Read, Result, Write};
+
+/// Writer of length-prefixed messages.
+///
+/// Each message is written as its byte length (`Varint<u64>`) followed by the payload,
+/// see `write_length_delimited`.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::{FramedReader, FramedWriter};
+///
+/// let mut w = FramedWriter::new(Vec::new());
+/// w.write_message(b"hello").unwrap();
+/// w.write_message(b"").unwrap();
+/// assert_eq!(7, w.bytes_written());
+///
+/// let buf = w.into_inner();
+/// let mut r = FramedReader::new(&buf[..], 16);
+/// assert_eq!(b"hello", &r.read_message().unwrap()[..]);
+/// assert!(r.read_message().unwrap().is_empty());
+/// ```
+#[derive(Debug)]
+pub struct FramedWriter<W> {
+    inner: W,
+    written: usize,
+}
+
+impl<W: Write> FramedWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner, written: 0 }
+    }
+
+    /// Write a single message, returns bytes written including the length prefix
+    pub fn write_message(&mut self, data: &[u8]) -> Result<usize> {
+        let size = write_length_delimited(&mut self.inner, data)?;
+        self.written += size;
+        Ok(size)
+    }
+
+    /// Total bytes written so far
+    pub fn bytes_written(&self) -> usize {
+        self.written
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// Reader of messages written by `FramedWriter`.
+///
+/// Messages longer than the maximum frame size fail with `InvalidData` before any allocation.
+#[derive(Debug)]
+pub struct FramedReader<R> {
+    inner: R,
+    max_frame_size: usize,
+}
+
+impl<R: Read> FramedReader<R> {
+    pub fn new(inner: R, max_frame_size: usize) -> Self {
+        Self {
+            inner,
+            max_frame_size,
+        }
+    }
+
+    /// Read a single message.
+    /// Fails with `UnexpectedEof` at the end of the stream or if the message is truncated.
+    pub fn read_message(&mut self) -> Result<Vec<u8>> {
+        read_length_delimited(&mut self.inner, self.max_frame_size)
+    }
+
+    pub fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
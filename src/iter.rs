@@ -0,0 +1,61 @@
+use crate::{VarintCodec, VarintError};
+use core::marker::PhantomData;
+
+/// Iterator decoding consecutive values from a byte slice.
+///
+/// Stops at the end of the slice. A truncated or invalid value yields a single error,
+/// after which the iterator is exhausted and `remainder` starts at that value.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::VarintIter;
+///
+/// let mut iter = VarintIter::<u32>::new(&[5, 241, 1, 249]);
+/// assert_eq!(Some(Ok(5)), iter.next());
+/// assert_eq!(Some(Ok(241)), iter.next());
+/// assert!(iter.next().unwrap().is_err());
+/// assert_eq!(None, iter.next());
+/// assert_eq!([249], iter.remainder());
+/// ```
+#[derive(Debug, Clone)]
+pub struct VarintIter<'a, T> {
+    buf: &'a [u8],
+    failed: bool,
+    _marker: PhantomData<T>,
+}
+
+impl<'a, T: VarintCodec> VarintIter<'a, T> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self {
+            buf,
+            failed: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Bytes not consumed yet
+    pub fn remainder(&self) -> &'a [u8] {
+        self.buf
+    }
+}
+
+impl<T: VarintCodec> Iterator for VarintIter<'_, T> {
+    type Item = Result<T, VarintError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.buf.is_empty() {
+            return None;
+        }
+        match T::decode_from_slice(self.buf) {
+            Ok((v, len)) => {
+                self.buf = &self.buf[len..];
+                Some(Ok(v))
+            }
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! LEB128 encoding for interop with WebAssembly, DWARF and other formats using it.
+//!
+//! The byte layout is unrelated to the default encoding of this crate: every byte carries
+//! 7 bits of the value, least significant group first, and the high bit marks that more bytes
+//! follow. The signed variant stores the two's complement value and sign-extends from the last
+//! group. Use these functions only for external LEB128 data, they can't read `write_varint` output.
+use std::io::{Error, ErrorKind, Read, Result, Write};
+
+const MAX_LEB128_LEN: usize = 10;
+
+#[inline]
+fn read_byte<R: Read + ?Sized>(r: &mut R) -> Result<u8> {
+    let mut byte = [0u8];
+    r.read_exact(&mut byte)?;
+    Ok(byte[0])
+}
+
+/// Length of the LEB128 value at the beginning of `buf` without decoding it,
+/// `None` if it does not end within `buf` or within the longest 64-bit encoding
+pub(crate) fn leb128_len(buf: &[u8]) -> Option<usize> {
+    buf.iter()
+        .take(MAX_LEB128_LEN)
+        .position(|b| b & 0x80 == 0)
+        .map(|i| i + 1)
+}
+
+/// Write an unsigned LEB128 value, returns bytes written
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::{read_uleb128, write_uleb128};
+///
+/// let mut buf = Vec::new();
+/// assert_eq!(3, write_uleb128(&mut buf, 624_485).unwrap());
+/// assert_eq!([0xe5, 0x8e, 0x26], buf[..]);
+/// assert_eq!(624_485, read_uleb128(&mut &buf[..]).unwrap());
+/// ```
+pub fn write_uleb128<W: Write + ?Sized>(w: &mut W, mut v: u64) -> Result<usize> {
+    let mut buf = [0u8; MAX_LEB128_LEN];
+    let mut len = 0;
+    loop {
+        let byte = (v & 0x7f) as u8;
+        v >>= 7;
+        if v == 0 {
+            buf[len] = byte;
+            len += 1;
+            break;
+        }
+        buf[len] = byte | 0x80;
+        len += 1;
+    }
+    w.write_all(&buf[..len])?;
+    Ok(len)
+}
+
+/// Read an unsigned LEB128 value.
+/// Fails with `InvalidData` if the value does not fit `u64`.
+pub fn read_uleb128<R: Read + ?Sized>(r: &mut R) -> Result<u64> {
+    let mut v = 0u64;
+    let mut shift = 0;
+    loop {
+        let byte = read_byte(r)?;
+        // the 10th byte holds the top bit only and must end the value
+        if shift == 63 && byte > 1 {
+            return Err(Error::from(ErrorKind::InvalidData));
+        }
+        v |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(v);
+        }
+        shift += 7;
+    }
+}
+
+/// Write a signed LEB128 value, returns bytes written
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::{read_sleb128, write_sleb128};
+///
+/// let mut buf = Vec::new();
+/// assert_eq!(3, write_sleb128(&mut buf, -123_456).unwrap());
+/// assert_eq!([0xc0, 0xbb, 0x78], buf[..]);
+/// assert_eq!(-123_456, read_sleb128(&mut &buf[..]).unwrap());
+/// ```
+pub fn write_sleb128<W: Write + ?Sized>(w: &mut W, mut v: i64) -> Result<usize> {
+    let mut buf = [0u8; MAX_LEB128_LEN];
+    let mut len = 0;
+    loop {
+        let byte = (v & 0x7f) as u8;
+        v >>= 7;
+        let sign = byte & 0x40 != 0;
+        if (v == 0 && !sign) || (v == -1 && sign) {
+            buf[len] = byte;
+            len += 1;
+            break;
+        }
+        buf[len] = byte | 0x80;
+        len += 1;
+    }
+    w.write_all(&buf[..len])?;
+    Ok(len)
+}
+
+/// Read a signed LEB128 value.
+/// Fails with `InvalidData` if the value does not fit `i64`.
+pub fn read_sleb128<R: Read + ?Sized>(r: &mut R) -> Result<i64> {
+    let mut v = 0i64;
+    let mut shift = 0;
+    loop {
+        let byte = read_byte(r)?;
+        // the 10th byte holds the top bit only and must end the value with the sign extended
+        if shift == 63 && byte != 0 && byte != 0x7f {
+            return Err(Error::from(ErrorKind::InvalidData));
+        }
+        v |= i64::from(byte & 0x7f) << shift;
+        shift += 7;
+        if byte & 0x80 == 0 {
+            if shift < 64 && byte & 0x40 != 0 {
+                v |= -1 << shift;
+            }
+            return Ok(v);
+        }
+    }
+}
@@ -28,7 +28,6 @@
 //! Use it:
 //!
 //! ```rust,no_run
-//! use std::mem;
 //! use std::io::Cursor;
 //!
 //! use varuint::*;
@@ -65,10 +64,134 @@
 //! Conversion method makes values closer to 0 to take less space.
 //! See [Protobuf docs](https://developers.google.com/protocol-buffers/docs/encoding#signed-integers)
 //! for details.
+//!
+//! `NonZero` unsigned integers are encoded as `V-1` of the underlying type, so `1` takes a single byte.
+//!
+//! Byte-wise comparison of encodings does not match the numeric order because of the little-endian
+//! forms. Use `write_varint_ordered`/`read_varint_ordered` for sorted key components, they store
+//! those payloads big-endian.
+//!
+//! ## `no_std`
+//!
+//! The `std` feature is enabled by default. Without it the crate builds on `core` only and
+//! provides `Varint::encode_into`/`Varint::decode_from` over byte slices instead of the
+//! `std::io` based traits. `SliceCursor`/`SliceCursorMut` read and write consecutive values.
+//! The `embedded-io` feature adds `read_varint_embedded`/`write_varint_embedded` over the
+//! `embedded_io` stream traits.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "tokio")]
+mod async_io;
+#[cfg(feature = "bigint")]
+mod bigint;
+#[cfg(feature = "bytes")]
+mod buf;
+#[cfg(feature = "std")]
+mod budget;
+mod bulk;
+#[cfg(feature = "std")]
+mod convenience;
+#[cfg(feature = "std")]
+mod counting;
+mod cursor;
+#[cfg(feature = "std")]
+mod decoder;
+#[cfg(feature = "std")]
+mod delta;
+#[cfg(feature = "std")]
+mod detect;
+#[cfg(feature = "embedded-io")]
+mod embedded;
+mod encoded;
+mod error;
+#[cfg(feature = "std")]
+mod framed;
+mod iter;
+#[cfg(feature = "std")]
+mod leb128;
+#[cfg(feature = "std")]
+mod macros;
+#[cfg(feature = "decode-observer")]
+mod observer;
+mod ordered;
+#[cfg(feature = "rayon")]
+mod parallel;
 mod read_write;
+#[cfg(feature = "std")]
+mod ring;
+#[cfg(feature = "std")]
 mod ser_deser;
+#[cfg(feature = "serde-support")]
+mod serde_support;
+#[cfg(feature = "test-utils")]
+mod testing;
+mod validated;
 mod varint;
+mod varuint;
+pub mod zigzag;
 
-pub use crate::read_write::{ReadVarint, VarintSizeHint, WriteVarint};
-pub use crate::ser_deser::{Deserializable, Serializable};
+#[cfg(feature = "tokio")]
+pub use crate::async_io::{peek_varint, read_varint_async, write_varint_async, VarintPeek};
+#[cfg(feature = "bigint")]
+pub use crate::bigint::{ReadVarintBig, WriteVarintBig};
+#[cfg(feature = "std")]
+pub use crate::budget::BudgetReader;
+pub use crate::bulk::decode_many;
+#[cfg(feature = "bytes")]
+pub use crate::buf::{VarintBuf, VarintBufMut};
+#[cfg(feature = "std")]
+pub use crate::convenience::{from_varint_slice, recode_canonical, to_varint_vec};
+#[cfg(feature = "std")]
+pub use crate::counting::CountingWriter;
+pub use crate::cursor::{SliceCursor, SliceCursorMut};
+#[cfg(feature = "std")]
+pub use crate::decoder::VarintDecoder;
+#[cfg(feature = "std")]
+pub use crate::delta::{
+    read_varint_deltas, read_zigzag_deltas, write_varint_deltas, write_zigzag_deltas,
+};
+#[cfg(feature = "std")]
+pub use crate::detect::{detect_scheme, Scheme};
+#[cfg(feature = "embedded-io")]
+pub use crate::embedded::{read_varint_embedded, write_varint_embedded, EmbeddedReadError};
+pub use crate::encoded::VarintBytes;
+pub use crate::error::VarintError;
+#[cfg(feature = "std")]
+pub use crate::framed::{FramedReader, FramedWriter};
+pub use crate::iter::VarintIter;
+#[cfg(feature = "std")]
+pub use crate::leb128::{read_sleb128, read_uleb128, write_sleb128, write_uleb128};
+#[cfg(feature = "decode-observer")]
+pub use crate::observer::set_decode_observer;
+pub use crate::ordered::{read_varint_ordered, write_varint_ordered, OrderedVarint};
+#[cfg(feature = "rayon")]
+pub use crate::parallel::par_decode_offsets;
+#[cfg(feature = "std")]
+pub use crate::read_write::{
+    encodings_equal, read_run_u64, read_varint_dynamic, read_varint_narrowing_info,
+    read_varint_to_buf, skip_varint, try_read_varint_bufread, validate_exact_count, varint_ranges,
+    write_run_u64, ReadVarint, WriteVarint,
+};
+pub use crate::read_write::{
+    cmp_encoded, encode_const_u64, read_varint_iter, read_varint_slice, read_varint_slice_strict,
+    skip_varint_slice, try_decode_small, varint_len_from_first_byte, varint_size, varint_size_i128,
+    varint_size_i16, varint_size_i32, varint_size_i64, varint_size_i8, varint_size_u128,
+    varint_size_u16, varint_size_u32, varint_size_u64, varint_size_u8, varint_to_varuint_128,
+    varint_to_varuint_16, varint_to_varuint_32, varint_to_varuint_64, varint_to_varuint_8,
+    varuint_to_varint_128, varuint_to_varint_16, varuint_to_varint_32, varuint_to_varint_64,
+    varuint_to_varint_8, write_varint_slice, NarrowFit, VarintCodec, VarintSizeHint, VarintValue,
+    MAX_VARINT_LEN,
+};
+#[cfg(feature = "std")]
+pub use crate::ring::{RingReader, RingWriter};
+#[cfg(feature = "std")]
+pub use crate::ser_deser::{
+    read_bool_vec, read_length_delimited, read_string, read_tagged_sign, read_varint_vec,
+    write_bool_vec, write_length_delimited, write_nested, write_string, write_tagged_sign,
+    write_varint_slice_prefixed, Deserializable, Serializable, VarintLen,
+};
+#[cfg(feature = "test-utils")]
+pub use crate::testing::check_str_roundtrip;
+pub use crate::validated::ValidatedVarint;
 pub use crate::varint::{Varint, VarintBaseType};
+pub use crate::varuint::Varuint;
@@ -0,0 +1,70 @@
+/// Define a distinct newtype over `Varint<$ty>` with the serialization traits forwarded.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::{varint_newtype, Deserializable, Serializable};
+///
+/// varint_newtype!(pub UserId, u64);
+///
+/// let id = UserId::from(300);
+/// let mut buf = Vec::new();
+/// assert_eq!(2, id.serialize(&mut buf).unwrap());
+/// assert_eq!(id, UserId::deserialize(&mut &buf[..]).unwrap());
+/// assert_eq!("300", id.to_string());
+/// ```
+#[macro_export]
+macro_rules! varint_newtype {
+    ($(#[$meta:meta])* $vis:vis $name:ident, $ty:ty) => {
+        $(#[$meta])*
+        #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Copy, Clone)]
+        $vis struct $name(pub $crate::Varint<$ty>);
+
+        impl $crate::Serializable for $name {
+            #[inline]
+            fn size_hint(&self) -> usize {
+                $crate::Serializable::size_hint(&self.0)
+            }
+
+            #[inline]
+            fn serialize(&self, w: &mut dyn ::std::io::Write) -> ::std::io::Result<usize> {
+                $crate::Serializable::serialize(&self.0, w)
+            }
+        }
+
+        impl $crate::Deserializable for $name {
+            #[inline]
+            fn deserialize(r: &mut dyn ::std::io::Read) -> ::std::io::Result<Self> {
+                <$crate::Varint<$ty> as $crate::Deserializable>::deserialize(r).map(Self)
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            #[inline]
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                ::std::fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        impl ::std::convert::From<$ty> for $name {
+            #[inline]
+            fn from(v: $ty) -> Self {
+                Self($crate::Varint(v))
+            }
+        }
+
+        impl ::std::convert::From<$crate::Varint<$ty>> for $name {
+            #[inline]
+            fn from(v: $crate::Varint<$ty>) -> Self {
+                Self(v)
+            }
+        }
+
+        impl ::std::convert::From<$name> for $ty {
+            #[inline]
+            fn from(v: $name) -> Self {
+                (v.0).0
+            }
+        }
+    };
+}
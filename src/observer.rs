@@ -0,0 +1,21 @@
+use std::sync::RwLock;
+
+type Observer = fn(u128, usize);
+
+static OBSERVER: RwLock<Option<Observer>> = RwLock::new(None);
+
+/// Set a process-wide callback invoked after every successful `read_varint`, `None` removes it.
+///
+/// The callback gets the unsigned value as it is stored on the wire
+/// (zigzag-encoded for signed types) and the number of bytes consumed.
+/// It is not called when decoding fails.
+pub fn set_decode_observer(f: Option<Observer>) {
+    *OBSERVER.write().unwrap_or_else(|e| e.into_inner()) = f;
+}
+
+#[inline]
+pub(crate) fn notify(v: u128, len: usize) {
+    if let Some(f) = *OBSERVER.read().unwrap_or_else(|e| e.into_inner()) {
+        f(v, len);
+    }
+}
@@ -0,0 +1,75 @@
+//! Order-preserving encoding for unsigned integers, suitable for sorted key components.
+//!
+//! The header byte and the 2 and 3-byte forms are the same as in the default encoding,
+//! but the payload of the 4..17-byte forms (leading bytes `249..=255`) is stored big-endian.
+//! Header bytes grow with the value and every value uses its minimal form, so comparing
+//! two encodings with `memcmp` gives the same result as comparing the values.
+//! Encodings are prefix-free, so the order holds for keys built by concatenation too.
+//!
+//! Signed types are not supported, zigzag mapping does not preserve order.
+use crate::read_write::varint_len_from_first_byte;
+use crate::{VarintCodec, VarintError, MAX_VARINT_LEN};
+
+/// Unsigned types supported by the order-preserving encoding
+pub trait OrderedVarint: VarintCodec + Copy {}
+
+macro_rules! impl_ordered {
+    ($($ty:ty),+) => {
+        $(impl OrderedVarint for $ty {})+
+    };
+}
+
+impl_ordered!(u8, u16, u32, u64, u128, usize);
+
+#[inline]
+fn swap_payload(buf: &mut [u8]) {
+    if buf.len() > 3 {
+        buf[1..].reverse();
+    }
+}
+
+/// Encode `v` to the beginning of `buf` in the order-preserving form, returns the number of bytes
+/// written. Fails with `BufferTooSmall` without writing anything if `buf` can't hold the value.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::write_varint_ordered;
+///
+/// let mut a = [0u8; 17];
+/// let mut b = [0u8; 17];
+/// let a_len = write_varint_ordered(&mut a, 0x02_00_10u32).unwrap();
+/// let b_len = write_varint_ordered(&mut b, 0x03_00_01u32).unwrap();
+/// assert_eq!([249, 0x02, 0x00, 0x10], a[..a_len]);
+/// assert!(a[..a_len] < b[..b_len]);
+/// ```
+#[inline]
+pub fn write_varint_ordered<T: OrderedVarint>(
+    buf: &mut [u8],
+    v: T,
+) -> core::result::Result<usize, VarintError> {
+    if buf.len() < v.varint_size() {
+        return Err(VarintError::BufferTooSmall);
+    }
+    let len = v.encode_to_slice(buf);
+    swap_payload(&mut buf[..len]);
+    Ok(len)
+}
+
+/// Decode an order-preserving value from the beginning of `buf`,
+/// returns it along with the number of bytes consumed.
+#[inline]
+pub fn read_varint_ordered<T: OrderedVarint>(
+    buf: &[u8],
+) -> core::result::Result<(T, usize), VarintError> {
+    let first = *buf.first().ok_or(VarintError::UnexpectedEof)?;
+    let len = varint_len_from_first_byte(first);
+    if len > T::MAX_ENCODED_LEN {
+        return Err(VarintError::Overflow);
+    }
+    let src = buf.get(..len).ok_or(VarintError::UnexpectedEof)?;
+    let mut tmp = [0u8; MAX_VARINT_LEN];
+    tmp[..len].copy_from_slice(src);
+    swap_payload(&mut tmp[..len]);
+    T::decode_from_slice(&tmp[..len])
+}
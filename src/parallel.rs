@@ -0,0 +1,29 @@
+use crate::read_write::varint_len_from_first_byte;
+use crate::ReadVarint;
+use rayon::prelude::*;
+use std::io::{Error, ErrorKind, Result};
+
+/// Decode a buffer of concatenated `u64` varints into offsets using all rayon threads.
+///
+/// Value boundaries are located with a sequential header-only scan first,
+/// then values are decoded in parallel. Fails if the buffer is malformed or
+/// a value does not fit `usize`.
+pub fn par_decode_offsets(buf: &[u8]) -> Result<Vec<usize>> {
+    let mut starts = Vec::new();
+    let mut pos = 0;
+    while pos < buf.len() {
+        starts.push(pos);
+        pos += varint_len_from_first_byte(buf[pos]);
+    }
+    if pos > buf.len() {
+        return Err(Error::from(ErrorKind::UnexpectedEof));
+    }
+    starts
+        .par_iter()
+        .with_min_len(1024)
+        .map(|&start| {
+            let v: u64 = (&buf[start..]).read_varint()?;
+            usize::try_from(v).map_err(|_| Error::from(ErrorKind::InvalidData))
+        })
+        .collect()
+}
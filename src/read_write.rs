@@ -1,6 +1,15 @@
-use std::convert::TryInto;
+use crate::VarintError;
+use core::num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize};
+#[cfg(feature = "std")]
+use core::convert::TryInto;
+#[cfg(feature = "std")]
+use core::ops::Range;
+#[cfg(feature = "std")]
 use std::io::{Error, ErrorKind, Result};
-use std::io::{Read, Write};
+#[cfg(feature = "std")]
+use std::io::{BufRead, Read, Write};
+#[cfg(feature = "std")]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Varuint size hinting trait
 pub trait VarintSizeHint {
@@ -9,527 +18,1697 @@ pub trait VarintSizeHint {
 
 impl VarintSizeHint for u8 {
     fn varint_size(self) -> usize {
-        if self <= 240 {
-            1
-        } else {
-            2
-        }
+        varint_size_u8(self)
     }
 }
 
 impl VarintSizeHint for u16 {
     fn varint_size(self) -> usize {
-        if self <= 240 {
-            1
-        } else if self <= 2031 {
-            2
-        } else {
-            3
-        }
+        varint_size_u16(self)
     }
 }
 
 impl VarintSizeHint for u32 {
     fn varint_size(self) -> usize {
-        if self <= 240 {
-            1
-        } else if self <= 2031 {
-            2
-        } else if self <= 67567 {
-            3
-        } else if self <= 16_777_215 {
-            4
-        } else {
-            5
-        }
+        varint_size_u32(self)
     }
 }
 
 impl VarintSizeHint for u64 {
     fn varint_size(self) -> usize {
-        if self <= 240 {
-            1
-        } else if self <= 2031 {
-            2
-        } else if self <= 67567 {
-            3
-        } else if self <= 16_777_215 {
-            4
-        } else if self <= 4_294_967_295 {
-            5
-        } else if self <= 1_099_511_627_775 {
-            6
-        } else if self <= 281_474_976_710_655 {
-            7
-        } else if self <= 72_057_594_037_927_935 {
-            8
-        } else {
-            9
-        }
+        varint_size_u64(self)
     }
 }
 
 impl VarintSizeHint for u128 {
     fn varint_size(self) -> usize {
-        if self <= 240 {
-            1
-        } else if self <= 2031 {
-            2
-        } else if self <= 67567 {
-            3
-        } else if self <= 16_777_215 {
-            4
-        } else if self <= 4_294_967_295 {
-            5
-        } else if self <= 1_099_511_627_775 {
-            6
-        } else if self <= 281_474_976_710_655 {
-            7
-        } else if self <= 72_057_594_037_927_935 {
-            8
-        } else if self <= 18_446_744_073_709_551_615 {
-            9
-        } else {
-            17
-        }
+        varint_size_u128(self)
     }
 }
 
 impl VarintSizeHint for i8 {
     fn varint_size(self) -> usize {
-        varint_to_varuint_8(self).varint_size()
+        varint_size_i8(self)
     }
 }
 
 impl VarintSizeHint for i16 {
     fn varint_size(self) -> usize {
-        varint_to_varuint_16(self).varint_size()
+        varint_size_i16(self)
     }
 }
 
 impl VarintSizeHint for i32 {
     fn varint_size(self) -> usize {
-        varint_to_varuint_32(self).varint_size()
+        varint_size_i32(self)
     }
 }
 
 impl VarintSizeHint for i64 {
     fn varint_size(self) -> usize {
-        varint_to_varuint_64(self).varint_size()
+        varint_size_i64(self)
     }
 }
 
 impl VarintSizeHint for i128 {
     fn varint_size(self) -> usize {
-        varint_to_varuint_128(self).varint_size()
+        varint_size_i128(self)
+    }
+}
+
+/// Encoded as `u64`, so the wire format does not depend on the platform
+impl VarintSizeHint for usize {
+    fn varint_size(self) -> usize {
+        varint_size_u64(self as u64)
     }
 }
 
+/// Encoded as `i64`, so the wire format does not depend on the platform
+impl VarintSizeHint for isize {
+    fn varint_size(self) -> usize {
+        varint_size_i64(self as i64)
+    }
+}
+
+impl<T: VarintSizeHint + Copy> VarintSizeHint for &T {
+    fn varint_size(self) -> usize {
+        (*self).varint_size()
+    }
+}
+
+/// Encoded byte-length of any value with a size hint, same as `v.varint_size()`
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::varint_size;
+///
+/// let total: usize = [5u64, 300, 70_000].iter().map(varint_size).sum();
+/// assert_eq!(1 + 2 + 4, total);
+/// ```
+#[inline]
+pub fn varint_size<T: VarintSizeHint>(v: T) -> usize {
+    v.varint_size()
+}
+
+/// Encoded byte-length of a `u8` value, usable in const contexts
+#[inline]
+pub const fn varint_size_u8(v: u8) -> usize {
+    if v <= 240 {
+        1
+    } else {
+        2
+    }
+}
+
+/// Encoded byte-length of a `u16` value, usable in const contexts
+#[inline]
+pub const fn varint_size_u16(v: u16) -> usize {
+    if v <= 240 {
+        1
+    } else if v <= 2031 {
+        2
+    } else {
+        3
+    }
+}
+
+/// Encoded byte-length of a `u32` value, usable in const contexts
+#[inline]
+pub const fn varint_size_u32(v: u32) -> usize {
+    if v <= 240 {
+        1
+    } else if v <= 2031 {
+        2
+    } else if v <= 67567 {
+        3
+    } else if v <= 16_777_215 {
+        4
+    } else {
+        5
+    }
+}
+
+/// Encoded byte-length of a `u64` value, usable in const contexts
+#[inline]
+pub const fn varint_size_u64(v: u64) -> usize {
+    if v <= 240 {
+        1
+    } else if v <= 2031 {
+        2
+    } else if v <= 67567 {
+        3
+    } else if v <= 16_777_215 {
+        4
+    } else if v <= 4_294_967_295 {
+        5
+    } else if v <= 1_099_511_627_775 {
+        6
+    } else if v <= 281_474_976_710_655 {
+        7
+    } else if v <= 72_057_594_037_927_935 {
+        8
+    } else {
+        9
+    }
+}
+
+/// Encoded byte-length of a `u128` value, usable in const contexts
+#[inline]
+pub const fn varint_size_u128(v: u128) -> usize {
+    if v <= 240 {
+        1
+    } else if v <= 2031 {
+        2
+    } else if v <= 67567 {
+        3
+    } else if v <= 16_777_215 {
+        4
+    } else if v <= 4_294_967_295 {
+        5
+    } else if v <= 1_099_511_627_775 {
+        6
+    } else if v <= 281_474_976_710_655 {
+        7
+    } else if v <= 72_057_594_037_927_935 {
+        8
+    } else if v <= 18_446_744_073_709_551_615 {
+        9
+    } else {
+        17
+    }
+}
+
+/// Encoded byte-length of a `i8` value, usable in const contexts
+#[inline]
+pub const fn varint_size_i8(v: i8) -> usize {
+    varint_size_u8(varint_to_varuint_8(v))
+}
+
+/// Encoded byte-length of a `i16` value, usable in const contexts
+#[inline]
+pub const fn varint_size_i16(v: i16) -> usize {
+    varint_size_u16(varint_to_varuint_16(v))
+}
+
+/// Encoded byte-length of a `i32` value, usable in const contexts
+#[inline]
+pub const fn varint_size_i32(v: i32) -> usize {
+    varint_size_u32(varint_to_varuint_32(v))
+}
+
+/// Encoded byte-length of a `i64` value, usable in const contexts
+#[inline]
+pub const fn varint_size_i64(v: i64) -> usize {
+    varint_size_u64(varint_to_varuint_64(v))
+}
+
+/// Encoded byte-length of a `i128` value, usable in const contexts
+#[inline]
+pub const fn varint_size_i128(v: i128) -> usize {
+    varint_size_u128(varint_to_varuint_128(v))
+}
+
+/// Maximum encoded byte-length of any varint
+pub const MAX_VARINT_LEN: usize = 17;
+
+/// Encoding primitives over byte slices, available without `std`
+pub trait VarintCodec: VarintSizeHint + Sized {
+    /// Maximum encoded byte-length of the type
+    const MAX_ENCODED_LEN: usize;
+
+    /// Whether the type is signed and encoded with zigzag mapping
+    const SIGNED: bool;
+
+    /// Bit width of the type
+    const BITS: u32;
+
+    /// Largest leading byte the canonical encoding of the type uses,
+    /// see `read_varint_slice_strict`
+    const MAX_LEADING_BYTE: u8 = max_leading_byte(Self::BITS);
+
+    /// Encode to the beginning of `buf`, returns the number of bytes written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buf` is shorter than `varint_size()`.
+    fn encode_to_slice(self, buf: &mut [u8]) -> usize;
+
+    /// Decode from the beginning of `buf`, returns the value and the number of bytes consumed
+    fn decode_from_slice(buf: &[u8]) -> core::result::Result<(Self, usize), VarintError>;
+}
+
+macro_rules! impl_codec_unsigned {
+    ($ty:ty, $max_len:expr) => {
+        impl VarintCodec for $ty {
+            const MAX_ENCODED_LEN: usize = $max_len;
+            const SIGNED: bool = false;
+            const BITS: u32 = <$ty>::BITS;
+
+            #[inline]
+            fn encode_to_slice(self, buf: &mut [u8]) -> usize {
+                encode_u128(u128::from(self), buf)
+            }
+
+            #[inline]
+            fn decode_from_slice(buf: &[u8]) -> core::result::Result<(Self, usize), VarintError> {
+                let (v, len) = decode_u128(buf, Self::MAX_ENCODED_LEN)?;
+                let v = <$ty>::try_from(v).map_err(|_| VarintError::Overflow)?;
+                Ok((v, len))
+            }
+        }
+    };
+}
+
+impl_codec_unsigned!(u8, 2);
+impl_codec_unsigned!(u16, 3);
+impl_codec_unsigned!(u32, 5);
+impl_codec_unsigned!(u64, 9);
+impl_codec_unsigned!(u128, MAX_VARINT_LEN);
+
+macro_rules! impl_codec_signed {
+    ($ty:ty, $uty:ty, $to_unsigned:ident, $to_signed:ident) => {
+        impl VarintCodec for $ty {
+            const MAX_ENCODED_LEN: usize = <$uty>::MAX_ENCODED_LEN;
+            const SIGNED: bool = true;
+            const BITS: u32 = <$ty>::BITS;
+
+            #[inline]
+            fn encode_to_slice(self, buf: &mut [u8]) -> usize {
+                $to_unsigned(self).encode_to_slice(buf)
+            }
+
+            #[inline]
+            fn decode_from_slice(buf: &[u8]) -> core::result::Result<(Self, usize), VarintError> {
+                let (v, len) = <$uty>::decode_from_slice(buf)?;
+                Ok(($to_signed(v), len))
+            }
+        }
+    };
+}
+
+impl_codec_signed!(i8, u8, varint_to_varuint_8, varuint_to_varint_8);
+impl_codec_signed!(i16, u16, varint_to_varuint_16, varuint_to_varint_16);
+impl_codec_signed!(i32, u32, varint_to_varuint_32, varuint_to_varint_32);
+impl_codec_signed!(i64, u64, varint_to_varuint_64, varuint_to_varint_64);
+impl_codec_signed!(i128, u128, varint_to_varuint_128, varuint_to_varint_128);
+
+macro_rules! impl_codec_pointer_sized {
+    ($ty:ty, $wide:ty) => {
+        impl VarintCodec for $ty {
+            const MAX_ENCODED_LEN: usize = <$wide>::MAX_ENCODED_LEN;
+            const SIGNED: bool = <$wide>::SIGNED;
+            const BITS: u32 = <$ty>::BITS;
+
+            #[inline]
+            fn encode_to_slice(self, buf: &mut [u8]) -> usize {
+                (self as $wide).encode_to_slice(buf)
+            }
+
+            #[inline]
+            fn decode_from_slice(buf: &[u8]) -> core::result::Result<(Self, usize), VarintError> {
+                let (v, len) = <$wide>::decode_from_slice(buf)?;
+                let v = <$ty>::try_from(v).map_err(|_| VarintError::Overflow)?;
+                Ok((v, len))
+            }
+        }
+    };
+}
+
+impl_codec_pointer_sized!(usize, u64);
+impl_codec_pointer_sized!(isize, i64);
+
+/// Decode a value from the beginning of `buf`, returns it along with the number of bytes consumed.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::read_varint_slice;
+///
+/// let buf = [5u8, 241, 1];
+/// let (a, n) = read_varint_slice::<u64>(&buf).unwrap();
+/// let (b, _) = read_varint_slice::<u64>(&buf[n..]).unwrap();
+/// assert_eq!((5, 241), (a, b));
+/// ```
+#[inline]
+pub fn read_varint_slice<T: VarintCodec>(
+    buf: &[u8],
+) -> core::result::Result<(T, usize), VarintError> {
+    T::decode_from_slice(buf)
+}
+
+/// Leading byte of the canonical encoding of the largest unsigned value of `bits` width
+const fn max_leading_byte(bits: u32) -> u8 {
+    if bits >= 128 {
+        return 255;
+    }
+    encode_const_u64(u64::MAX >> (64 - bits)).0[0]
+}
+
+/// Like `read_varint_slice`, but accepts only the leading bytes a canonical encoder produces
+/// for the width of `T`, failing with `InvalidLeadingByte` for the rest before anything else
+/// is checked. Accepted leading bytes are `0..=T::MAX_LEADING_BYTE`:
+///
+/// | Type             | Accepted leading bytes   |
+/// |------------------|--------------------------|
+/// | `u8`, `i8`       | `0..=241`                |
+/// | `u16`, `i16`     | `0..=248`                |
+/// | `u32`, `i32`     | `0..=250`                |
+/// | `u64`, `i64`     | `0..=254`                |
+/// | `u128`, `i128`   | `0..=255`                |
+/// | `usize`, `isize` | as for the pointer width |
+///
+/// The payload is not checked, use `ValidatedVarint` to also reject padded encodings.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::{read_varint_slice, read_varint_slice_strict, VarintError};
+///
+/// assert_eq!(Ok((255u8, 2)), read_varint_slice_strict(&[241, 15]));
+/// assert_eq!(Err(VarintError::InvalidLeadingByte(242)), read_varint_slice_strict::<u8>(&[242]));
+/// // the loose decoder needs the payload to find out the value overflows
+/// assert_eq!(Err(VarintError::UnexpectedEof), read_varint_slice::<u8>(&[242]));
+/// ```
+#[inline]
+pub fn read_varint_slice_strict<T: VarintCodec>(
+    buf: &[u8],
+) -> core::result::Result<(T, usize), VarintError> {
+    let first = *buf.first().ok_or(VarintError::UnexpectedEof)?;
+    if first > T::MAX_LEADING_BYTE {
+        return Err(VarintError::InvalidLeadingByte(first));
+    }
+    T::decode_from_slice(buf)
+}
+
+/// Encode `v` to the beginning of `buf`, returns the number of bytes written.
+/// Fails with `BufferTooSmall` without writing anything if `buf` can't hold the value.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::{write_varint_slice, VarintError};
+///
+/// let mut buf = [0u8; 17];
+/// assert_eq!(Ok(2), write_varint_slice(&mut buf, 241u32));
+/// assert_eq!([241, 1], buf[..2]);
+/// assert_eq!(Err(VarintError::BufferTooSmall), write_varint_slice(&mut buf[..1], 241u32));
+/// ```
+#[inline]
+pub fn write_varint_slice<T: VarintCodec + Copy>(
+    buf: &mut [u8],
+    v: T,
+) -> core::result::Result<usize, VarintError> {
+    if buf.len() < v.varint_size() {
+        return Err(VarintError::BufferTooSmall);
+    }
+    Ok(v.encode_to_slice(buf))
+}
+
+/// Length of the value at the beginning of `buf` without decoding it.
+/// Fails with `UnexpectedEof` if `buf` ends before the value does.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::skip_varint_slice;
+///
+/// let buf = [250u8, 1, 2, 3, 4, 7];
+/// let n = skip_varint_slice(&buf).unwrap();
+/// assert_eq!([7], buf[n..]);
+/// ```
+#[inline]
+pub fn skip_varint_slice(buf: &[u8]) -> core::result::Result<usize, VarintError> {
+    let first = *buf.first().ok_or(VarintError::UnexpectedEof)?;
+    let len = varint_len_from_first_byte(first);
+    if buf.len() < len {
+        return Err(VarintError::UnexpectedEof);
+    }
+    Ok(len)
+}
+
+/// Consume the next value from `r` without decoding it, returns the number of bytes skipped
+#[cfg(feature = "std")]
+pub fn skip_varint<R: Read + ?Sized>(r: &mut R) -> Result<usize> {
+    read_value_bytes(r, &mut [0u8; MAX_VARINT_LEN], within(MAX_VARINT_LEN))
+}
+
+/// Decode the next value from the buffered data of `r`, consuming it only once it is complete.
+/// Returns `None` without consuming anything if the buffered bytes end mid-value
+/// or the input is exhausted, so the call can be retried once more data arrives.
+///
+/// A leading byte implying a form too wide for `T` fails with `InvalidData` right away.
+///
+/// Note that `std::io::BufReader` refills only an empty buffer, so a value straddling the end
+/// of its buffer is never completed by it. The function suits `BufRead` implementations that
+/// append incoming data to the unconsumed bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::try_read_varint_bufread;
+///
+/// let mut r = &[241u8, 1, 248, 0][..];
+/// assert_eq!(Some(241u32), try_read_varint_bufread(&mut r).unwrap());
+/// assert_eq!(None, try_read_varint_bufread::<u32, _>(&mut r).unwrap());
+/// assert_eq!([248, 0], r);
+/// ```
+#[cfg(feature = "std")]
+pub fn try_read_varint_bufread<T: VarintCodec, R: BufRead + ?Sized>(
+    r: &mut R,
+) -> Result<Option<T>> {
+    let buf = r.fill_buf()?;
+    let first = match buf.first() {
+        Some(&b) => b,
+        None => return Ok(None),
+    };
+    let len = varint_len_from_first_byte(first);
+    if len > T::MAX_ENCODED_LEN {
+        return Err(VarintError::Overflow.into());
+    }
+    if buf.len() < len {
+        return Ok(None);
+    }
+    let (v, _) = T::decode_from_slice(&buf[..len])?;
+    r.consume(len);
+    Ok(Some(v))
+}
+
+/// Read the next value from `r` keeping its raw bytes in `buf`,
+/// returns the decoded value and the number of bytes used.
+/// Fails with `UnexpectedEof` if the reader ends mid-value.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::read_varint_to_buf;
+///
+/// let mut buf = [0u8; 17];
+/// let (v, len) = read_varint_to_buf(&mut &[248u8, 0, 1, 5][..], &mut buf).unwrap();
+/// assert_eq!((2033, 3), (v, len));
+/// assert_eq!([248, 0, 1], buf[..len]);
+/// ```
+#[cfg(feature = "std")]
+pub fn read_varint_to_buf<R: Read + ?Sized>(
+    r: &mut R,
+    buf: &mut [u8; MAX_VARINT_LEN],
+) -> Result<(u128, usize)> {
+    let len = read_value_bytes(r, buf, within(MAX_VARINT_LEN))?;
+    Ok(u128::decode_from_slice(&buf[..len])?)
+}
+
+/// Decode a value pulling exactly its encoded bytes from `it`.
+///
+/// Fails with `UnexpectedEof` if the iterator runs dry mid-value and with `Overflow`
+/// before pulling the rest if the leading byte implies a form too wide for `T`.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::read_varint_iter;
+///
+/// let mut it = [241u8, 1, 5].iter().copied();
+/// assert_eq!(Ok(241u32), read_varint_iter(&mut it));
+/// assert_eq!(Ok(5u32), read_varint_iter(&mut it));
+/// assert_eq!(None, it.next());
+/// ```
+pub fn read_varint_iter<T: VarintCodec>(
+    it: &mut impl Iterator<Item = u8>,
+) -> core::result::Result<T, VarintError> {
+    let mut buf = [0u8; MAX_VARINT_LEN];
+    buf[0] = it.next().ok_or(VarintError::UnexpectedEof)?;
+    let len = varint_len_from_first_byte(buf[0]);
+    if len > T::MAX_ENCODED_LEN {
+        return Err(VarintError::Overflow);
+    }
+    for b in &mut buf[1..len] {
+        *b = it.next().ok_or(VarintError::UnexpectedEof)?;
+    }
+    T::decode_from_slice(&buf[..len]).map(|(v, _)| v)
+}
+
+#[inline]
+fn encode_u128(v: u128, buf: &mut [u8]) -> usize {
+    match u64::try_from(v) {
+        Ok(v) => {
+            let (bytes, size) = encode_const_u64(v);
+            buf[..size].copy_from_slice(&bytes[..size]);
+            size
+        }
+        Err(_) => {
+            buf[0] = 255;
+            buf[1..17].copy_from_slice(&v.to_le_bytes());
+            17
+        }
+    }
+}
+
+#[inline]
+fn decode_u128(buf: &[u8], max_len: usize) -> core::result::Result<(u128, usize), VarintError> {
+    let first = *buf.first().ok_or(VarintError::UnexpectedEof)?;
+    let len = varint_len_from_first_byte(first);
+    if len > max_len {
+        return Err(VarintError::Overflow);
+    }
+    let body = buf.get(1..len).ok_or(VarintError::UnexpectedEof)?;
+    let v = match len {
+        1 => u128::from(first),
+        2 => u128::from(decode_two_byte(first, body[0])),
+        3 => 2032 + 256 * u128::from(body[0]) + u128::from(body[1]),
+        5 => u128::from(u32::from_le_bytes(body.try_into().unwrap())),
+        9 => u128::from(u64::from_le_bytes(body.try_into().unwrap())),
+        17 => u128::from_le_bytes(body.try_into().unwrap()),
+        _ => read_value_128(body),
+    };
+    Ok((v, len))
+}
+
+#[cfg(feature = "std")]
 pub trait WriteVarint<T> {
     fn write_varint(&mut self, v: T) -> Result<usize>;
+
+    /// Write value using at least `min_width` bytes, returns bytes written.
+    ///
+    /// Widths below the minimal one are padded to the next little-endian form (4..9 or 17 bytes).
+    /// The result is not canonical, but `ReadVarint` accepts it as long as the form fits the type,
+    /// so the value may be overwritten in place later without shifting data.
+    /// Fails with `InvalidInput` if no such form exists for the type.
+    /// The default implementation, left to implementors outside this crate, always fails.
+    fn write_varint_min_width(&mut self, _v: T, _min_width: usize) -> Result<usize> {
+        Err(Error::from(ErrorKind::InvalidInput))
+    }
+
+    /// Write `min(v, max)`, returns bytes written
+    fn write_varint_clamped(&mut self, v: T, max: T) -> Result<usize>
+    where
+        T: Ord,
+    {
+        self.write_varint(v.min(max))
+    }
+
+    /// Write a run of `count` copies of `value` as a value and count pair, returns bytes written.
+    /// Generalizes `write_run_u64` to any value type, the count is always a `u64` varint.
+    fn write_run(&mut self, value: T, count: u64) -> Result<usize>
+    where
+        Self: WriteVarint<u64>,
+    {
+        let size = self.write_varint(value)?;
+        Ok(size + WriteVarint::<u64>::write_varint(self, count)?)
+    }
+
+    /// Write value prefixed with its encoded byte length, so readers may skip it without decoding.
+    /// Returns total bytes written.
+    fn write_self_delimited(&mut self, v: T) -> Result<usize>
+    where
+        T: VarintSizeHint + Copy,
+        Self: WriteVarint<u8>,
+    {
+        let prefix = WriteVarint::<u8>::write_varint(self, v.varint_size() as u8)?;
+        Ok(prefix + self.write_varint(v)?)
+    }
 }
 
+#[cfg(feature = "std")]
 impl<T: Write + ?Sized> WriteVarint<u8> for T {
     fn write_varint(&mut self, v: u8) -> Result<usize> {
-        let size = v.varint_size();
-        match size {
-            1 => self.write_all(&[v])?,
-            2 => {
-                self.write_all(&[241, (v - 240) as u8])?;
-            }
-            _ => unreachable!(),
-        }
-        Ok(size)
+        write_encoded(self, v)
+    }
+
+    fn write_varint_min_width(&mut self, v: u8, min_width: usize) -> Result<usize> {
+        write_min_width(self, u128::from(v), min_width, u8::MAX_ENCODED_LEN)
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: Write + ?Sized> WriteVarint<u16> for T {
     fn write_varint(&mut self, v: u16) -> Result<usize> {
-        let size = v.varint_size();
-        match size {
-            1 => {
-                self.write_all(&[v as u8])?
-            },
-            2 => {
-                self.write_all(&[((v - 240) / 256 + 241) as u8, ((v - 240) % 256) as u8])?;
-            }
-            3 => {
-                self.write_all(&[248, ((v - 2032) / 256) as u8, ((v - 2032) % 256) as u8])?;
-            }
-            _ => unreachable!(),
-        }
-        Ok(size)
+        write_encoded(self, v)
+    }
+
+    fn write_varint_min_width(&mut self, v: u16, min_width: usize) -> Result<usize> {
+        write_min_width(self, u128::from(v), min_width, u16::MAX_ENCODED_LEN)
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: Write + ?Sized> WriteVarint<u32> for T {
     fn write_varint(&mut self, v: u32) -> Result<usize> {
-        let size = v.varint_size();
-        match size {
-            1 => self.write_all(&[v as u8])?,
-            2 => {
-                self.write_all(&[((v - 240) / 256 + 241) as u8, ((v - 240) % 256) as u8])?;
-            }
-            3 => {
-                self.write_all(&[
-                    248,
-                    ((v - 2032) / 256) as u8,
-                    ((v - 2032) % 256) as u8,
-                ])?;
-            }
-            4 => {
-                self.write_all(&[249])?;
-                self.write_all(&v.to_le_bytes()[0..3])?;
-            }
-            5 => {
-                self.write_all(&[250])?;
-                self.write_all(&v.to_le_bytes()[0..4])?;
-            }
-            _ => unreachable!(),
-        }
-        Ok(size)
+        write_encoded(self, v)
+    }
+
+    fn write_varint_min_width(&mut self, v: u32, min_width: usize) -> Result<usize> {
+        write_min_width(self, u128::from(v), min_width, u32::MAX_ENCODED_LEN)
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: Write + ?Sized> WriteVarint<u64> for T {
     fn write_varint(&mut self, v: u64) -> Result<usize> {
-        let size = v.varint_size();
-        match size {
-            1 => self.write_all(&[v as u8])?,
-            2 => {
-                self.write_all(&[((v - 240) / 256 + 241) as u8, ((v - 240) % 256) as u8])?;
-            }
-            3 => {
-                self.write_all(&[
-                    248,
-                    ((v - 2032) / 256) as u8,
-                    ((v - 2032) % 256) as u8,
-                ])?;
-            }
-            4 => {
-                self.write_all(&[249])?;
-                self.write_all(&v.to_le_bytes()[0..3])?;
-            }
-            5 => {
-                self.write_all(&[250])?;
-                self.write_all(&v.to_le_bytes()[0..4])?;
-            }
-            6 => {
-                self.write_all(&[251])?;
-                self.write_all(&v.to_le_bytes()[0..5])?;
-            }
-            7 => {
-                self.write_all(&[252])?;
-                self.write_all(&v.to_le_bytes()[0..6])?;
-            }
-            8 => {
-                self.write_all(&[253])?;
-                self.write_all(&v.to_le_bytes()[0..7])?;
-            }
-            9 => {
-                self.write_all(&[254])?;
-                self.write_all(&v.to_le_bytes()[0..8])?;
-            }
-            _ => unreachable!(),
-        }
-        Ok(size)
+        write_encoded(self, v)
+    }
+
+    fn write_varint_min_width(&mut self, v: u64, min_width: usize) -> Result<usize> {
+        write_min_width(self, u128::from(v), min_width, u64::MAX_ENCODED_LEN)
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: Write + ?Sized> WriteVarint<u128> for T {
     fn write_varint(&mut self, v: u128) -> Result<usize> {
-        let size = v.varint_size();
-        match size {
-            1 => self.write_all(&[v as u8])?,
-            2 => {
-                self.write_all(&[((v - 240) / 256 + 241) as u8, ((v - 240) % 256) as u8])?;
-            }
-            3 => {
-                self.write_all(&[
-                    248,
-                    ((v - 2032) / 256) as u8,
-                    ((v - 2032) % 256) as u8,
-                ])?;
-            }
-            4 => {
-                self.write_all(&[249])?;
-                self.write_all(&v.to_le_bytes()[0..3])?;
-            }
-            5 => {
-                self.write_all(&[250])?;
-                self.write_all(&v.to_le_bytes()[0..4])?;
-            }
-            6 => {
-                self.write_all(&[251])?;
-                self.write_all(&v.to_le_bytes()[0..5])?;
-            }
-            7 => {
-                self.write_all(&[252])?;
-                self.write_all(&v.to_le_bytes()[0..6])?;
-            }
-            8 => {
-                self.write_all(&[253])?;
-                self.write_all(&v.to_le_bytes()[0..7])?;
-            }
-            9 => {
-                self.write_all(&[254])?;
-                self.write_all(&v.to_le_bytes()[0..8])?;
-            }
-            17 => {
-                self.write_all(&[255])?;
-                self.write_all(&v.to_le_bytes()[0..16])?;
-            }
-            _ => unreachable!(),
-        }
-        Ok(size)
+        write_encoded(self, v)
+    }
+
+    fn write_varint_min_width(&mut self, v: u128, min_width: usize) -> Result<usize> {
+        write_min_width(self, v, min_width, u128::MAX_ENCODED_LEN)
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: Write + ?Sized> WriteVarint<i8> for T {
     fn write_varint(&mut self, v: i8) -> Result<usize> {
         self.write_varint(varint_to_varuint_8(v))
     }
+
+    fn write_varint_min_width(&mut self, v: i8, min_width: usize) -> Result<usize> {
+        self.write_varint_min_width(varint_to_varuint_8(v), min_width)
+    }
 }
 
+#[cfg(feature = "std")]
 impl<T: Write + ?Sized> WriteVarint<i16> for T {
     fn write_varint(&mut self, v: i16) -> Result<usize> {
         self.write_varint(varint_to_varuint_16(v))
     }
+
+    fn write_varint_min_width(&mut self, v: i16, min_width: usize) -> Result<usize> {
+        self.write_varint_min_width(varint_to_varuint_16(v), min_width)
+    }
 }
 
+#[cfg(feature = "std")]
 impl<T: Write + ?Sized> WriteVarint<i32> for T {
     fn write_varint(&mut self, v: i32) -> Result<usize> {
         self.write_varint(varint_to_varuint_32(v))
     }
+
+    fn write_varint_min_width(&mut self, v: i32, min_width: usize) -> Result<usize> {
+        self.write_varint_min_width(varint_to_varuint_32(v), min_width)
+    }
 }
 
+#[cfg(feature = "std")]
 impl<T: Write + ?Sized> WriteVarint<i64> for T {
     fn write_varint(&mut self, v: i64) -> Result<usize> {
         self.write_varint(varint_to_varuint_64(v))
     }
+
+    fn write_varint_min_width(&mut self, v: i64, min_width: usize) -> Result<usize> {
+        self.write_varint_min_width(varint_to_varuint_64(v), min_width)
+    }
 }
 
+#[cfg(feature = "std")]
 impl<T: Write + ?Sized> WriteVarint<i128> for T {
     fn write_varint(&mut self, v: i128) -> Result<usize> {
         self.write_varint(varint_to_varuint_128(v))
     }
+
+    fn write_varint_min_width(&mut self, v: i128, min_width: usize) -> Result<usize> {
+        self.write_varint_min_width(varint_to_varuint_128(v), min_width)
+    }
+}
+
+/// Written as `u64`
+#[cfg(feature = "std")]
+impl<T: Write + ?Sized> WriteVarint<usize> for T {
+    fn write_varint(&mut self, v: usize) -> Result<usize> {
+        self.write_varint(v as u64)
+    }
+
+    fn write_varint_min_width(&mut self, v: usize, min_width: usize) -> Result<usize> {
+        self.write_varint_min_width(v as u64, min_width)
+    }
+}
+
+/// Written as `i64`
+#[cfg(feature = "std")]
+impl<T: Write + ?Sized> WriteVarint<isize> for T {
+    fn write_varint(&mut self, v: isize) -> Result<usize> {
+        self.write_varint(v as i64)
+    }
+
+    fn write_varint_min_width(&mut self, v: isize, min_width: usize) -> Result<usize> {
+        self.write_varint_min_width(v as i64, min_width)
+    }
+}
+
+/// Written the same way as the referenced value
+#[cfg(feature = "std")]
+impl<'a, T: WriteVarint<V> + Write + ?Sized, V: Copy> WriteVarint<&'a V> for T {
+    fn write_varint(&mut self, v: &'a V) -> Result<usize> {
+        self.write_varint(*v)
+    }
+
+    fn write_varint_min_width(&mut self, v: &'a V, min_width: usize) -> Result<usize> {
+        self.write_varint_min_width(*v, min_width)
+    }
 }
 
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn write_encoded<W: Write + ?Sized, V: VarintCodec>(w: &mut W, v: V) -> Result<usize> {
+    let mut buf = [0u8; MAX_VARINT_LEN];
+    let size = v.encode_to_slice(&mut buf);
+    w.write_all(&buf[..size])?;
+    Ok(size)
+}
+
+#[cfg(feature = "std")]
+fn write_min_width<W: Write + ?Sized>(
+    w: &mut W,
+    v: u128,
+    min_width: usize,
+    max_width: usize,
+) -> Result<usize> {
+    if v.varint_size() >= min_width {
+        return w.write_varint(v);
+    }
+    let width = match min_width {
+        0..=4 => 4,
+        5..=9 => min_width,
+        10..=17 => 17,
+        _ => return Err(Error::from(ErrorKind::InvalidInput)),
+    };
+    if width > max_width {
+        return Err(Error::from(ErrorKind::InvalidInput));
+    }
+    let header = if width == 17 { 255 } else { 245 + width as u8 };
+    w.write_all(&[header])?;
+    w.write_all(&v.to_le_bytes()[..width - 1])?;
+    Ok(width)
+}
+
+/// Write a run of `count` copies of the `u64` `value`, returns bytes written.
+/// Same as `WriteVarint::write_run`, which accepts values of any varint type.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::{read_run_u64, write_run_u64};
+///
+/// let mut buf = Vec::new();
+/// assert_eq!(3, write_run_u64(&mut buf, 300, 4).unwrap());
+/// assert_eq!(vec![300; 4], read_run_u64(&mut &buf[..], 16).unwrap());
+/// ```
+#[cfg(feature = "std")]
+pub fn write_run_u64<W: Write + ?Sized>(w: &mut W, value: u64, count: u64) -> Result<usize> {
+    w.write_run(value, count)
+}
+
+/// Read and expand a run of `u64` values written by `write_run_u64`.
+/// Same as `ReadVarint::read_run`, which accepts values of any varint type.
+/// Fails with `InvalidData` if the run is longer than `max_total`.
+#[cfg(feature = "std")]
+pub fn read_run_u64<R: Read + ?Sized>(r: &mut R, max_total: usize) -> Result<Vec<u64>> {
+    r.read_run(max_total)
+}
+
+#[cfg(feature = "std")]
 pub trait ReadVarint<T> {
+    /// Read a value.
+    /// Fails with `InvalidData` carrying `VarintError::Overflow` if the leading byte implies
+    /// a form too wide for `T` or the decoded value does not fit `T`.
     fn read_varint(&mut self) -> Result<T>;
-}
 
-impl<T: Read + ?Sized> ReadVarint<u8> for T {
-    fn read_varint(&mut self) -> Result<u8> {
-        let mut buf = [0u8; 2];
-        self.read_exact(&mut buf[0..1])?;
-        let length = match buf[0] {
-            v @ 0..=240 => return Ok(v),
-            241..=247 => 2,
-            _ => return Err(Error::from(ErrorKind::InvalidData)),
-        };
-        self.read_exact(&mut buf[1..length])?;
-        Ok(match length {
-            2 => 240u8 + buf[1],
-            _ => unreachable!(),
-        })
-    }
-}
-
-impl<T: Read + ?Sized> ReadVarint<u16> for T {
-    fn read_varint(&mut self) -> Result<u16> {
-        let mut buf: [u8; 3] = [0u8; 3];
-        self.read_exact(&mut buf[0..1])?;
-        let length = match buf[0] {
-            v @ 0..=240 => return Ok(u16::from(v)),
-            241..=247 => 2,
-            248 => 3,
-            _ => return Err(Error::from(ErrorKind::InvalidData)),
-        };
-        self.read_exact(&mut buf[1..length])?;
-        Ok(match length {
-            2 => 240u16 + 256u16 * (u16::from(buf[0]) - 241u16) + u16::from(buf[1]),
-            3 => 2032u16 + 256u16 * u16::from(buf[1]) + u16::from(buf[2]),
-            _ => unreachable!(),
-        })
-    }
-}
-
-impl<T: Read + ?Sized> ReadVarint<u32> for T {
-    fn read_varint(&mut self) -> Result<u32> {
-        let mut buf: [u8; 5] = [0u8; 5];
-        self.read_exact(&mut buf[0..1])?;
-        let length = match buf[0] {
-            v @ 0..=240 => return Ok(u32::from(v)),
-            241..=247 => 2,
-            248 => 3,
-            249 => 4,
-            250 => 5,
-            _ => return Err(Error::from(ErrorKind::InvalidData)),
-        };
-        self.read_exact(&mut buf[1..length])?;
-        Ok(match length {
-            2 => 240u32 + 256u32 * (u32::from(buf[0]) - 241u32) + u32::from(buf[1]),
-            3 => 2032u32 + 256u32 * u32::from(buf[1]) + u32::from(buf[2]),
-            4 => read_value_32(&buf[1..=3]),
-            5 => {
-                u32::from_le_bytes(buf[1..].try_into().unwrap())
-            }
-            _ => unreachable!(),
-        })
-    }
-}
-
-impl<T: Read + ?Sized> ReadVarint<u64> for T {
-    fn read_varint(&mut self) -> Result<u64> {
-        let mut buf: [u8; 9] = [0u8; 9];
-        self.read_exact(&mut buf[0..1])?;
-        let length = match buf[0] {
-            v @ 0..=240 => return Ok(u64::from(v)),
-            241..=247 => 2,
-            248 => 3,
-            249 => 4,
-            250 => 5,
-            251 => 6,
-            252 => 7,
-            253 => 8,
-            254 => 9,
-            _ => return Err(Error::from(ErrorKind::InvalidData)),
-        };
-        self.read_exact(&mut buf[1..length])?;
-        Ok(match length {
-            2 => 240u64 + 256u64 * (u64::from(buf[0]) - 241u64) + u64::from(buf[1]),
-            3 => 2032u64 + 256u64 * u64::from(buf[1]) + u64::from(buf[2]),
-            4 => read_value_64(&buf[1..=3]),
-            5 => {
-                u64::from(u32::from_le_bytes(buf[1..5].try_into().unwrap()))
+    /// Read values until `sentinel` is met, the sentinel itself is not returned.
+    /// Fails with `InvalidData` if more than `max_count` values precede the sentinel.
+    fn read_until_sentinel(&mut self, sentinel: T, max_count: usize) -> Result<Vec<T>>
+    where
+        T: PartialEq,
+    {
+        let mut values = Vec::new();
+        loop {
+            let v = self.read_varint()?;
+            if v == sentinel {
+                return Ok(values);
             }
-            6 => read_value_64(&buf[1..=5]),
-            7 => read_value_64(&buf[1..=6]),
-            8 => read_value_64(&buf[1..=7]),
-            9 => {
-                u64::from_le_bytes(buf[1..].try_into().unwrap())
+            if values.len() == max_count {
+                return Err(Error::from(ErrorKind::InvalidData));
             }
-            _ => unreachable!(),
-        })
-    }
-}
-
-impl<T: Read + ?Sized> ReadVarint<u128> for T {
-    fn read_varint(&mut self) -> Result<u128> {
-        let mut buf: [u8; 17] = [0u8; 17];
-        self.read_exact(&mut buf[0..1])?;
-        let length = match buf[0] {
-            v @ 0..=240 => return Ok(u128::from(v)),
-            241..=247 => 2,
-            248 => 3,
-            249 => 4,
-            250 => 5,
-            251 => 6,
-            252 => 7,
-            253 => 8,
-            254 => 9,
-            255 => 17,
-        };
-        self.read_exact(&mut buf[1..length])?;
-        Ok(match length {
-            2 => 240u128 + 256u128 * (u128::from(buf[0]) - 241u128) + u128::from(buf[1]),
-            3 => 2032u128 + 256u128 * u128::from(buf[1]) + u128::from(buf[2]),
-            4 => read_value_128(&buf[1..=3]),
-            5 => {
-                u128::from(u32::from_le_bytes(buf[1..=4].try_into().unwrap()))
+            values.push(v);
+        }
+    }
+
+    /// Read exactly `N` values into an array.
+    /// Stops at the first error, no partially initialized array is ever exposed.
+    fn read_array<const N: usize>(&mut self) -> Result<[T; N]> {
+        let mut values: [Option<T>; N] = std::array::from_fn(|_| None);
+        for v in &mut values {
+            *v = Some(self.read_varint()?);
+        }
+        Ok(values.map(Option::unwrap))
+    }
+
+    /// Read and expand a run written by `WriteVarint::write_run`.
+    /// Fails with `InvalidData` if the run is longer than `max_total`.
+    /// Generalizes `read_run_u64` to any value type.
+    fn read_run(&mut self, max_total: usize) -> Result<Vec<T>>
+    where
+        T: Clone,
+        Self: ReadVarint<u64>,
+    {
+        let value = self.read_varint()?;
+        let count = ReadVarint::<u64>::read_varint(self)?;
+        match usize::try_from(count) {
+            Ok(count) if count <= max_total => Ok(vec![value; count]),
+            _ => Err(Error::from(ErrorKind::InvalidData)),
+        }
+    }
+
+    /// Read the next value, skipping bytes that are not a valid leading byte for `T`
+    /// and whole varints that are not a valid value of it, such as a `u8` above 255.
+    /// Returns `None` at the end of input, including a value truncated by it, also for values
+    /// spanning several varints such as `Duration`.
+    ///
+    /// This is a best-effort tool for salvaging corrupt data: any byte accepted as a leading
+    /// byte resynchronizes the stream, so it may resync to a false boundary and return garbage.
+    fn recover_next(&mut self) -> Result<Option<T>>
+    where
+        Self: Read,
+        for<'a> &'a [u8]: ReadVarint<T>,
+    {
+        let mut buf = Vec::with_capacity(MAX_VARINT_LEN);
+        loop {
+            buf.clear();
+            // composite values such as `Duration` span several varints, read them one by one
+            loop {
+                let start = buf.len();
+                buf.push(0);
+                if !read_exact_or_end(self, &mut buf[start..])? {
+                    return Ok(None);
+                }
+                let res: Result<T> = (&buf[..]).read_varint();
+                match res {
+                    Ok(v) => return Ok(Some(v)),
+                    Err(e) if e.kind() == ErrorKind::InvalidData && start == 0 => break,
+                    Err(e) if e.kind() != ErrorKind::UnexpectedEof => return Err(e),
+                    Err(_) => {}
+                }
+                let len = varint_len_from_first_byte(buf[start]);
+                if len == 1 {
+                    continue;
+                }
+                buf.resize(start + len, 0);
+                if !read_exact_or_end(self, &mut buf[start + 1..])? {
+                    return Ok(None);
+                }
+                let res: Result<T> = (&buf[..]).read_varint();
+                match res {
+                    Ok(v) => return Ok(Some(v)),
+                    Err(e) if e.kind() == ErrorKind::InvalidData && start == 0 => break,
+                    Err(e) if e.kind() != ErrorKind::UnexpectedEof => return Err(e),
+                    Err(_) => {}
+                }
             }
-            6 => read_value_128(&buf[1..=5]),
-            7 => read_value_128(&buf[1..=6]),
-            8 => read_value_128(&buf[1..=7]),
-            9 => {
-                u128::from(u64::from_le_bytes(buf[1..=8].try_into().unwrap()))
+        }
+    }
+
+    /// Read value written by `WriteVarint::write_self_delimited`.
+    /// Fails with `InvalidData` if the length prefix does not match the value encoding.
+    fn read_self_delimited(&mut self) -> Result<T>
+    where
+        Self: Read,
+        for<'a> &'a [u8]: ReadVarint<T>,
+    {
+        let len = usize::from(ReadVarint::<u8>::read_varint(self)?);
+        if len > 17 {
+            return Err(Error::from(ErrorKind::InvalidData));
+        }
+        let mut buf = [0u8; 17];
+        self.read_exact(&mut buf[..len])?;
+        let mut body = &buf[..len];
+        let v = body.read_varint().map_err(|e| match e.kind() {
+            ErrorKind::UnexpectedEof => Error::from(ErrorKind::InvalidData),
+            _ => e,
+        })?;
+        if !body.is_empty() {
+            return Err(Error::from(ErrorKind::InvalidData));
+        }
+        Ok(v)
+    }
+
+    /// Read value, failing with `InvalidData` if it is not encoded in the minimal number of bytes.
+    /// A leading byte implying a form too wide for `T` is rejected with `Overflow`
+    /// before reading the rest of the value.
+    fn read_varint_canonical(&mut self) -> Result<T>
+    where
+        Self: Read,
+        T: VarintCodec + Copy,
+    {
+        match self.read_varint_checked()? {
+            (v, true) => Ok(v),
+            (_, false) => Err(VarintError::NonCanonical.into()),
+        }
+    }
+
+    /// Read value along with whether it is encoded in the minimal number of bytes,
+    /// accepting non-canonical encodings unlike `read_varint_canonical`.
+    /// A leading byte implying a form too wide for `T` is rejected with `Overflow`
+    /// before reading the rest of the value.
+    fn read_varint_checked(&mut self) -> Result<(T, bool)>
+    where
+        Self: Read,
+        T: VarintCodec + Copy,
+    {
+        let mut buf = [0u8; MAX_VARINT_LEN];
+        let len = read_value_bytes(self, &mut buf, within(T::MAX_ENCODED_LEN))?;
+        let (v, _) = T::decode_from_slice(&buf[..len])?;
+        Ok((v, v.varint_size() == len))
+    }
+
+    /// Read a value encoded in at most `max_len` bytes.
+    /// A leading byte implying a longer form is rejected with `InvalidData` before anything else
+    /// is read, carrying `VarintError::InvalidLeadingByte` if the form exceeds `max_len`
+    /// and `VarintError::Overflow` if it is too wide for `T`.
+    fn read_varint_limited(&mut self, max_len: usize) -> Result<T>
+    where
+        Self: Read,
+        T: VarintCodec,
+    {
+        let mut buf = [0u8; MAX_VARINT_LEN];
+        let len = read_value_bytes(self, &mut buf, |first, len| {
+            within(T::MAX_ENCODED_LEN)(first, len)?;
+            if len > max_len {
+                return Err(VarintError::InvalidLeadingByte(first));
             }
-            17 => {
-                u128::from_le_bytes(buf[1..].try_into().unwrap())
+            Ok(())
+        })?;
+        Ok(T::decode_from_slice(&buf[..len])?.0)
+    }
+
+    /// Read a value accepting only the leading bytes a canonical encoder produces for `T`,
+    /// see `read_varint_slice_strict` for the accepted bytes per width.
+    /// Other leading bytes fail with `InvalidData` carrying `VarintError::InvalidLeadingByte`
+    /// before the rest of the value is read.
+    fn read_varint_strict(&mut self) -> Result<T>
+    where
+        Self: Read,
+        T: VarintCodec,
+    {
+        let mut buf = [0u8; MAX_VARINT_LEN];
+        let len = read_value_bytes(self, &mut buf, |first, _| {
+            if first > T::MAX_LEADING_BYTE {
+                return Err(VarintError::InvalidLeadingByte(first));
             }
-            _ => unreachable!(),
-        })
+            Ok(())
+        })?;
+        Ok(T::decode_from_slice(&buf[..len])?.0)
+    }
+
+    /// Read value, reporting the exact failure reason as `VarintError`.
+    /// A leading byte implying a form too wide for `T` is rejected with `Overflow`
+    /// before reading the rest of the value.
+    fn try_read_varint(&mut self) -> core::result::Result<T, VarintError>
+    where
+        Self: Read,
+        T: VarintCodec,
+    {
+        let mut buf = [0u8; MAX_VARINT_LEN];
+        let len = read_value_bytes(self, &mut buf, within(T::MAX_ENCODED_LEN))?;
+        T::decode_from_slice(&buf[..len]).map(|(v, _)| v)
     }
 }
 
+/// `read_exact` returning `false` at the end of input
+#[cfg(feature = "std")]
+fn read_exact_or_end<R: Read + ?Sized>(r: &mut R, buf: &mut [u8]) -> Result<bool> {
+    match r.read_exact(buf) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Read the bytes of one value into `buf`, returns its length.
+/// `check` gets the leading byte and the length it implies, the rest of the value
+/// is read only if it passes.
+#[cfg(feature = "std")]
+#[inline]
+fn read_value_bytes<R, F>(r: &mut R, buf: &mut [u8; MAX_VARINT_LEN], check: F) -> Result<usize>
+where
+    R: Read + ?Sized,
+    F: FnOnce(u8, usize) -> core::result::Result<(), VarintError>,
+{
+    r.read_exact(&mut buf[..1])?;
+    let len = varint_len_from_first_byte(buf[0]);
+    check(buf[0], len)?;
+    // skipping the empty read keeps single-byte values cheap
+    if len > 1 {
+        r.read_exact(&mut buf[1..len])?;
+    }
+    Ok(len)
+}
+
+/// Leading byte check for `read_value_bytes` rejecting forms longer than `max_len` with `Overflow`
+#[cfg(feature = "std")]
+#[inline]
+fn within(max_len: usize) -> impl Fn(u8, usize) -> core::result::Result<(), VarintError> {
+    move |_, len| {
+        if len > max_len {
+            return Err(VarintError::Overflow);
+        }
+        Ok(())
+    }
+}
+
+/// Shared decoder of the unsigned `ReadVarint` impls and of `Varint<T>` deserialization,
+/// reads exactly the bytes of one value.
+/// A leading byte implying a form too wide for `V` is rejected before reading the rest.
+/// The decode observer gets the stored unsigned value, zigzag-encoded for signed types.
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn read_encoded<R: Read + ?Sized, V: VarintCodec>(r: &mut R) -> Result<V> {
+    let mut buf = [0u8; MAX_VARINT_LEN];
+    let len = read_value_bytes(r, &mut buf, within(V::MAX_ENCODED_LEN))?;
+    let (v, _) = V::decode_from_slice(&buf[..len])?;
+    #[cfg(feature = "decode-observer")]
+    if let Ok((raw, _)) = decode_u128(&buf[..len], len) {
+        crate::observer::notify(raw, len);
+    }
+    Ok(v)
+}
+
+macro_rules! impl_read_unsigned {
+    ($($ty:ty),+) => {
+        $(
+            #[cfg(feature = "std")]
+            impl<T: Read + ?Sized> ReadVarint<$ty> for T {
+                fn read_varint(&mut self) -> Result<$ty> {
+                    read_encoded(self)
+                }
+            }
+        )+
+    };
+}
+
+impl_read_unsigned!(u8, u16, u32, u64, u128);
+
+#[cfg(feature = "std")]
 impl<T: Read + ?Sized> ReadVarint<i8> for T {
     fn read_varint(&mut self) -> Result<i8> {
         Ok(varuint_to_varint_8(self.read_varint()?))
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: Read + ?Sized> ReadVarint<i16> for T {
     fn read_varint(&mut self) -> Result<i16> {
         Ok(varuint_to_varint_16(self.read_varint()?))
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: Read + ?Sized> ReadVarint<i32> for T {
     fn read_varint(&mut self) -> Result<i32> {
         Ok(varuint_to_varint_32(self.read_varint()?))
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: Read + ?Sized> ReadVarint<i64> for T {
     fn read_varint(&mut self) -> Result<i64> {
         Ok(varuint_to_varint_64(self.read_varint()?))
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: Read + ?Sized> ReadVarint<i128> for T {
     fn read_varint(&mut self) -> Result<i128> {
         Ok(varuint_to_varint_128(self.read_varint()?))
     }
 }
 
+/// Read as `u64`, fails with `InvalidData` if the value exceeds the platform's `usize`
+#[cfg(feature = "std")]
+impl<T: Read + ?Sized> ReadVarint<usize> for T {
+    fn read_varint(&mut self) -> Result<usize> {
+        let v: u64 = self.read_varint()?;
+        usize::try_from(v).map_err(|_| VarintError::Overflow.into())
+    }
+}
+
+/// Read as `i64`, fails with `InvalidData` if the value exceeds the platform's `isize`
+#[cfg(feature = "std")]
+impl<T: Read + ?Sized> ReadVarint<isize> for T {
+    fn read_varint(&mut self) -> Result<isize> {
+        let v: i64 = self.read_varint()?;
+        isize::try_from(v).map_err(|_| VarintError::Overflow.into())
+    }
+}
+
+/// Non-zero values are encoded as `value - 1` of the underlying type,
+/// so the whole value range shifts down and `1` takes a single byte.
+/// Reading fails with `InvalidData` if the decoded value is the type maximum.
+macro_rules! impl_non_zero {
+    ($nz:ty, $ty:ty) => {
+        impl VarintSizeHint for $nz {
+            fn varint_size(self) -> usize {
+                (self.get() - 1).varint_size()
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl<T: Write + ?Sized> WriteVarint<$nz> for T {
+            fn write_varint(&mut self, v: $nz) -> Result<usize> {
+                self.write_varint(v.get() - 1)
+            }
+
+            fn write_varint_min_width(&mut self, v: $nz, min_width: usize) -> Result<usize> {
+                self.write_varint_min_width(v.get() - 1, min_width)
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl<T: Read + ?Sized> ReadVarint<$nz> for T {
+            fn read_varint(&mut self) -> Result<$nz> {
+                let v: $ty = self.read_varint()?;
+                v.checked_add(1)
+                    .and_then(<$nz>::new)
+                    .ok_or_else(|| Error::from(ErrorKind::InvalidData))
+            }
+        }
+    };
+}
+
+impl_non_zero!(NonZeroU8, u8);
+impl_non_zero!(NonZeroU16, u16);
+impl_non_zero!(NonZeroU32, u32);
+impl_non_zero!(NonZeroU64, u64);
+impl_non_zero!(NonZeroU128, u128);
+impl_non_zero!(NonZeroUsize, usize);
+
+/// Always a single byte
+impl VarintSizeHint for bool {
+    fn varint_size(self) -> usize {
+        1
+    }
+}
+
+/// Written as a single `0` or `1` byte
+#[cfg(feature = "std")]
+impl<T: Write + ?Sized> WriteVarint<bool> for T {
+    fn write_varint(&mut self, v: bool) -> Result<usize> {
+        self.write_varint(u8::from(v))
+    }
+
+    fn write_varint_min_width(&mut self, v: bool, min_width: usize) -> Result<usize> {
+        self.write_varint_min_width(u8::from(v), min_width)
+    }
+}
+
+/// Fails with `InvalidData` for any value other than `0` and `1`
+#[cfg(feature = "std")]
+impl<T: Read + ?Sized> ReadVarint<bool> for T {
+    fn read_varint(&mut self) -> Result<bool> {
+        let v: u8 = self.read_varint()?;
+        match v {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(Error::from(ErrorKind::InvalidData)),
+        }
+    }
+}
+
+/// Encoded as the `u32` code point
+impl VarintSizeHint for char {
+    fn varint_size(self) -> usize {
+        varint_size_u32(u32::from(self))
+    }
+}
+
+/// Written as the `u32` code point
+#[cfg(feature = "std")]
+impl<T: Write + ?Sized> WriteVarint<char> for T {
+    fn write_varint(&mut self, v: char) -> Result<usize> {
+        self.write_varint(u32::from(v))
+    }
+
+    fn write_varint_min_width(&mut self, v: char, min_width: usize) -> Result<usize> {
+        self.write_varint_min_width(u32::from(v), min_width)
+    }
+}
+
+/// Fails with `InvalidData` if the code point is a surrogate or above `char::MAX`
+#[cfg(feature = "std")]
+impl<T: Read + ?Sized> ReadVarint<char> for T {
+    fn read_varint(&mut self) -> Result<char> {
+        let v: u32 = self.read_varint()?;
+        char::from_u32(v).ok_or_else(|| Error::from(ErrorKind::InvalidData))
+    }
+}
+
+// Floats are encoded as their IEEE 754 bit pattern, so the round trip is bit-exact (NaN payloads
+// and the sign of zero included). This is not a magnitude-based compaction: only `0.0` takes
+// a single byte, most other values take the full width of the bit pattern.
+macro_rules! impl_float {
+    ($ty:ty, $bits:ty, $size:ident) => {
+        /// Encoded as the bit pattern from `to_bits`
+        impl VarintSizeHint for $ty {
+            fn varint_size(self) -> usize {
+                $size(self.to_bits())
+            }
+        }
+
+        /// Written as the bit pattern from `to_bits`
+        #[cfg(feature = "std")]
+        impl<T: Write + ?Sized> WriteVarint<$ty> for T {
+            fn write_varint(&mut self, v: $ty) -> Result<usize> {
+                self.write_varint(v.to_bits())
+            }
+
+            fn write_varint_min_width(&mut self, v: $ty, min_width: usize) -> Result<usize> {
+                self.write_varint_min_width(v.to_bits(), min_width)
+            }
+        }
+
+        /// Read as a bit pattern and converted with `from_bits`
+        #[cfg(feature = "std")]
+        impl<T: Read + ?Sized> ReadVarint<$ty> for T {
+            fn read_varint(&mut self) -> Result<$ty> {
+                let v: $bits = self.read_varint()?;
+                Ok(<$ty>::from_bits(v))
+            }
+        }
+    };
+}
+
+impl_float!(f32, u32, varint_size_u32);
+impl_float!(f64, u64, varint_size_u64);
+
+/// Encoded as `as_secs` followed by `subsec_nanos`
+#[cfg(feature = "std")]
+impl VarintSizeHint for Duration {
+    fn varint_size(self) -> usize {
+        varint_size_u64(self.as_secs()) + varint_size_u32(self.subsec_nanos())
+    }
+}
+
+/// Written as `as_secs` (`u64`) followed by `subsec_nanos` (`u32`)
+#[cfg(feature = "std")]
+impl<T: Write + ?Sized> WriteVarint<Duration> for T {
+    fn write_varint(&mut self, v: Duration) -> Result<usize> {
+        Ok(self.write_varint(v.as_secs())? + self.write_varint(v.subsec_nanos())?)
+    }
+
+    fn write_varint_min_width(&mut self, v: Duration, min_width: usize) -> Result<usize> {
+        Ok(self.write_varint_min_width(v.as_secs(), min_width)?
+            + self.write_varint_min_width(v.subsec_nanos(), min_width)?)
+    }
+}
+
+/// Fails with `InvalidData` if the nanoseconds are not below one second
+#[cfg(feature = "std")]
+impl<T: Read + ?Sized> ReadVarint<Duration> for T {
+    fn read_varint(&mut self) -> Result<Duration> {
+        let secs: u64 = self.read_varint()?;
+        let nanos: u32 = self.read_varint()?;
+        if nanos >= 1_000_000_000 {
+            return Err(Error::from(ErrorKind::InvalidData));
+        }
+        Ok(Duration::new(secs, nanos))
+    }
+}
+
+/// Written as the `Duration` since `UNIX_EPOCH`.
+/// Fails with `InvalidInput` for a time before the epoch.
+#[cfg(feature = "std")]
+impl<T: Write + ?Sized> WriteVarint<SystemTime> for T {
+    fn write_varint(&mut self, v: SystemTime) -> Result<usize> {
+        self.write_varint(since_epoch(v)?)
+    }
+
+    fn write_varint_min_width(&mut self, v: SystemTime, min_width: usize) -> Result<usize> {
+        self.write_varint_min_width(since_epoch(v)?, min_width)
+    }
+}
+
+#[cfg(feature = "std")]
+fn since_epoch(v: SystemTime) -> Result<Duration> {
+    v.duration_since(UNIX_EPOCH)
+        .map_err(|_| Error::from(ErrorKind::InvalidInput))
+}
+
+/// Fails with `InvalidData` if the time is not representable on the platform
+#[cfg(feature = "std")]
+impl<T: Read + ?Sized> ReadVarint<SystemTime> for T {
+    fn read_varint(&mut self) -> Result<SystemTime> {
+        let d: Duration = self.read_varint()?;
+        UNIX_EPOCH
+            .checked_add(d)
+            .ok_or_else(|| Error::from(ErrorKind::InvalidData))
+    }
+}
+
+/// Smallest unsigned integer type a value fits
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Copy, Clone)]
+pub enum NarrowFit {
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+}
+
+impl NarrowFit {
+    pub fn of(v: u128) -> Self {
+        if v <= u128::from(u8::MAX) {
+            NarrowFit::U8
+        } else if v <= u128::from(u16::MAX) {
+            NarrowFit::U16
+        } else if v <= u128::from(u32::MAX) {
+            NarrowFit::U32
+        } else if v <= u128::from(u64::MAX) {
+            NarrowFit::U64
+        } else {
+            NarrowFit::U128
+        }
+    }
+}
+
+/// Read an unsigned value along with the smallest type it fits
+#[cfg(feature = "std")]
+pub fn read_varint_narrowing_info<R: Read + ?Sized>(r: &mut R) -> Result<(u128, NarrowFit)> {
+    let v: u128 = r.read_varint()?;
+    Ok((v, NarrowFit::of(v)))
+}
+
+/// Unsigned value stored in the smallest type it fits
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Copy, Clone)]
+pub enum VarintValue {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+}
+
+impl VarintValue {
+    /// Type the value is stored in
+    pub fn fit(self) -> NarrowFit {
+        match self {
+            VarintValue::U8(_) => NarrowFit::U8,
+            VarintValue::U16(_) => NarrowFit::U16,
+            VarintValue::U32(_) => NarrowFit::U32,
+            VarintValue::U64(_) => NarrowFit::U64,
+            VarintValue::U128(_) => NarrowFit::U128,
+        }
+    }
+}
+
+impl From<u128> for VarintValue {
+    fn from(v: u128) -> Self {
+        match NarrowFit::of(v) {
+            NarrowFit::U8 => VarintValue::U8(v as u8),
+            NarrowFit::U16 => VarintValue::U16(v as u16),
+            NarrowFit::U32 => VarintValue::U32(v as u32),
+            NarrowFit::U64 => VarintValue::U64(v as u64),
+            NarrowFit::U128 => VarintValue::U128(v),
+        }
+    }
+}
+
+impl From<VarintValue> for u128 {
+    fn from(v: VarintValue) -> Self {
+        match v {
+            VarintValue::U8(v) => u128::from(v),
+            VarintValue::U16(v) => u128::from(v),
+            VarintValue::U32(v) => u128::from(v),
+            VarintValue::U64(v) => u128::from(v),
+            VarintValue::U128(v) => v,
+        }
+    }
+}
+
+/// Read an unsigned value into the smallest type that holds it.
+/// The type depends on the value only, not on the encoded length.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::{read_varint_dynamic, VarintValue};
+///
+/// // 300 padded to the 9-byte form
+/// let buf = [254u8, 0x2c, 0x01, 0, 0, 0, 0, 0, 0];
+/// assert_eq!(VarintValue::U16(300), read_varint_dynamic(&mut &buf[..]).unwrap());
+/// ```
+#[cfg(feature = "std")]
+pub fn read_varint_dynamic<R: Read + ?Sized>(r: &mut R) -> Result<VarintValue> {
+    let v: u128 = r.read_varint()?;
+    Ok(VarintValue::from(v))
+}
+
+/// Check that `buf` holds exactly `n` complete varints and nothing else.
+/// Only header bytes are inspected, values are not decoded.
+#[cfg(feature = "std")]
+pub fn validate_exact_count(buf: &[u8], n: usize) -> Result<()> {
+    let mut pos = 0;
+    for _ in 0..n {
+        let first = match buf.get(pos) {
+            Some(b) => *b,
+            None => return Err(Error::from(ErrorKind::InvalidData)),
+        };
+        pos += varint_len_from_first_byte(first);
+        if pos > buf.len() {
+            return Err(Error::from(ErrorKind::UnexpectedEof));
+        }
+    }
+    if pos != buf.len() {
+        return Err(Error::from(ErrorKind::InvalidData));
+    }
+    Ok(())
+}
+
+/// Check whether two buffers, each holding a single varint, encode the same unsigned value.
+/// Non-canonical encodings are accepted, identical buffers are not decoded.
+#[cfg(feature = "std")]
+pub fn encodings_equal(a: &[u8], b: &[u8]) -> Result<bool> {
+    if a == b {
+        validate_exact_count(a, 1)?;
+        return Ok(true);
+    }
+    Ok(decode_single(a)? == decode_single(b)?)
+}
+
+#[cfg(feature = "std")]
+fn decode_single(mut buf: &[u8]) -> Result<u128> {
+    let v = buf.read_varint()?;
+    if !buf.is_empty() {
+        return Err(Error::from(ErrorKind::InvalidData));
+    }
+    Ok(v)
+}
+
+/// Compare the unsigned values of two buffers, each holding a single varint.
+/// Returns `None` if either buffer is not exactly one valid encoding.
+///
+/// Two encodings of the same 1..3-byte form compare byte-wise without decoding, other pairs are
+/// decoded, the 2-byte form starts at 240 and overlaps the single-byte one.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::cmp::Ordering;
+/// use varuint::cmp_encoded;
+///
+/// assert_eq!(Some(Ordering::Less), cmp_encoded(&[239], &[241, 0]));
+/// assert_eq!(Some(Ordering::Equal), cmp_encoded(&[240], &[241, 0]));
+/// assert_eq!(Some(Ordering::Greater), cmp_encoded(&[250, 0, 0, 0, 1], &[249, 0, 0, 2]));
+/// assert_eq!(None, cmp_encoded(&[241], &[0]));
+/// ```
+pub fn cmp_encoded(a: &[u8], b: &[u8]) -> Option<core::cmp::Ordering> {
+    let a_len = varint_len_from_first_byte(*a.first()?);
+    let b_len = varint_len_from_first_byte(*b.first()?);
+    if a_len != a.len() || b_len != b.len() {
+        return None;
+    }
+    // within one of the short forms the header grows with the value
+    // and the rest is stored big-endian
+    if a_len == b_len && a_len <= 3 {
+        return Some(a.cmp(b));
+    }
+    let (a, _) = u128::decode_from_slice(a).ok()?;
+    let (b, _) = u128::decode_from_slice(b).ok()?;
+    Some(a.cmp(&b))
+}
+
+/// Iterate over byte ranges of concatenated varints in `buf` without decoding them.
+/// Yields an `UnexpectedEof` error and stops if the last value is truncated.
+#[cfg(feature = "std")]
+pub fn varint_ranges(buf: &[u8]) -> impl Iterator<Item = Result<Range<usize>>> + '_ {
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        let start = pos;
+        let first = *buf.get(start)?;
+        let end = start + varint_len_from_first_byte(first);
+        if end > buf.len() {
+            pos = buf.len();
+            return Some(Err(Error::from(ErrorKind::UnexpectedEof)));
+        }
+        pos = end;
+        Some(Ok(start..end))
+    })
+}
+
+/// Encode `u64` value in a const context, returns encoded bytes and their count
+pub const fn encode_const_u64(v: u64) -> ([u8; 9], usize) {
+    let mut buf = [0u8; 9];
+    if v <= 240 {
+        buf[0] = v as u8;
+        return (buf, 1);
+    }
+    if v <= 2031 {
+        buf[0] = ((v - 240) / 256 + 241) as u8;
+        buf[1] = ((v - 240) % 256) as u8;
+        return (buf, 2);
+    }
+    if v <= 67567 {
+        buf[0] = 248;
+        buf[1] = ((v - 2032) / 256) as u8;
+        buf[2] = ((v - 2032) % 256) as u8;
+        return (buf, 3);
+    }
+    let size = if v <= 16_777_215 {
+        4
+    } else if v <= 4_294_967_295 {
+        5
+    } else if v <= 1_099_511_627_775 {
+        6
+    } else if v <= 281_474_976_710_655 {
+        7
+    } else if v <= 72_057_594_037_927_935 {
+        8
+    } else {
+        9
+    };
+    buf[0] = 245 + size as u8;
+    let bytes = v.to_le_bytes();
+    let mut i = 1;
+    while i < size {
+        buf[i] = bytes[i - 1];
+        i += 1;
+    }
+    (buf, size)
+}
+
+#[inline]
+const fn decode_two_byte(first: u8, second: u8) -> u64 {
+    240 + 256 * (first - 241) as u64 + second as u64
+}
+
+/// Decode a 1 or 2-byte value from the beginning of `buf` in a const context,
+/// returns the value and the number of bytes consumed.
+/// Returns `None` for longer forms and for a truncated value.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::try_decode_small;
+///
+/// const SMALL: Option<(u64, usize)> = try_decode_small(&[241, 1, 0]);
+/// assert_eq!(Some((241, 2)), SMALL);
+/// assert_eq!(None, try_decode_small(&[248, 0, 0]));
+/// ```
+pub const fn try_decode_small(buf: &[u8]) -> Option<(u64, usize)> {
+    if buf.is_empty() {
+        return None;
+    }
+    match buf[0] {
+        first @ 0..=240 => Some((first as u64, 1)),
+        first @ 241..=247 if buf.len() >= 2 => Some((decode_two_byte(first, buf[1]), 2)),
+        _ => None,
+    }
+}
+
+/// Total encoded byte-length of a varint given its leading byte
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::varint_len_from_first_byte;
+///
+/// const SCRATCH: [u8; varint_len_from_first_byte(255)] = [0; 17];
+/// assert_eq!(1, varint_len_from_first_byte(240));
+/// assert_eq!(4, varint_len_from_first_byte(249));
+/// assert_eq!(SCRATCH.len(), 17);
+/// ```
+#[inline(always)]
+pub const fn varint_len_from_first_byte(b: u8) -> usize {
+    match b {
+        0..=240 => 1,
+        241..=247 => 2,
+        248 => 3,
+        249..=254 => b as usize - 245,
+        255 => 17,
+    }
+}
+
+/// ZigZag-encode an `i8`, usable in const contexts
 #[inline(always)]
-fn varint_to_varuint_8(v: i8) -> u8 {
+pub const fn varint_to_varuint_8(v: i8) -> u8 {
     ((v << 1) ^ (v >> 7)) as u8
 }
 
+/// Decode a ZigZag-encoded `u8`, usable in const contexts
 #[inline(always)]
-fn varuint_to_varint_8(v: u8) -> i8 {
+pub const fn varuint_to_varint_8(v: u8) -> i8 {
     ((v >> 1) as i8) ^ -((v & 1) as i8)
 }
 
+/// ZigZag-encode an `i16`, usable in const contexts
 #[inline(always)]
-fn varint_to_varuint_16(v: i16) -> u16 {
+pub const fn varint_to_varuint_16(v: i16) -> u16 {
     ((v << 1) ^ (v >> 15)) as u16
 }
 
+/// Decode a ZigZag-encoded `u16`, usable in const contexts
 #[inline(always)]
-fn varuint_to_varint_16(v: u16) -> i16 {
+pub const fn varuint_to_varint_16(v: u16) -> i16 {
     ((v >> 1) as i16) ^ -((v & 1) as i16)
 }
 
+/// ZigZag-encode an `i32`, usable in const contexts
 #[inline(always)]
-fn varint_to_varuint_32(v: i32) -> u32 {
+pub const fn varint_to_varuint_32(v: i32) -> u32 {
     ((v << 1) ^ (v >> 31)) as u32
 }
 
+/// Decode a ZigZag-encoded `u32`, usable in const contexts
 #[inline(always)]
-fn varuint_to_varint_32(v: u32) -> i32 {
+pub const fn varuint_to_varint_32(v: u32) -> i32 {
     ((v >> 1) as i32) ^ -((v & 1) as i32)
 }
 
+/// ZigZag-encode an `i64`, usable in const contexts
 #[inline(always)]
-fn varint_to_varuint_64(v: i64) -> u64 {
+pub const fn varint_to_varuint_64(v: i64) -> u64 {
     ((v << 1) ^ (v >> 63)) as u64
 }
 
+/// Decode a ZigZag-encoded `u64`, usable in const contexts
 #[inline(always)]
-fn varuint_to_varint_64(v: u64) -> i64 {
+pub const fn varuint_to_varint_64(v: u64) -> i64 {
     ((v >> 1) as i64) ^ -((v & 1) as i64)
 }
 
+/// ZigZag-encode an `i128`, usable in const contexts
 #[inline(always)]
-fn varint_to_varuint_128(v: i128) -> u128 {
+pub const fn varint_to_varuint_128(v: i128) -> u128 {
     ((v << 1) ^ (v >> 127)) as u128
 }
 
+/// Decode a ZigZag-encoded `u128`, usable in const contexts
 #[inline(always)]
-fn varuint_to_varint_128(v: u128) -> i128 {
+pub const fn varuint_to_varint_128(v: u128) -> i128 {
     ((v >> 1) as i128) ^ -((v & 1) as i128)
 }
 
@@ -542,20 +1721,3 @@ fn read_value_128(buf: &[u8]) -> u128 {
     v
 }
 
-#[inline(always)]
-fn read_value_64(buf: &[u8]) -> u64 {
-    let mut v = 0;
-    for (i, val) in buf.iter().enumerate() {
-        v |= u64::from(*val) << (8 * i);
-    }
-    v
-}
-
-#[inline(always)]
-fn read_value_32(buf: &[u8]) -> u32 {
-    let mut v = 0;
-    for (i, val) in buf.iter().enumerate() {
-        v |= u32::from(*val) << (8 * i);
-    }
-    v
-}
@@ -0,0 +1,81 @@
+use std::io::{Read, Result, Write};
+
+/// Writer into a fixed-capacity circular buffer.
+///
+/// The head wraps to the start of the buffer, overwriting the oldest data,
+/// so an encoded value may straddle the end of the buffer.
+#[derive(Debug)]
+pub struct RingWriter<'a> {
+    buf: &'a mut [u8],
+    head: usize,
+}
+
+impl<'a> RingWriter<'a> {
+    pub fn new(buf: &'a mut [u8], head: usize) -> Self {
+        let head = if buf.is_empty() { 0 } else { head % buf.len() };
+        Self { buf, head }
+    }
+
+    /// Position of the next byte to be written
+    pub fn head(&self) -> usize {
+        self.head
+    }
+}
+
+impl Write for RingWriter<'_> {
+    fn write(&mut self, data: &[u8]) -> Result<usize> {
+        if self.buf.is_empty() {
+            return Ok(0);
+        }
+        for &b in data {
+            self.buf[self.head] = b;
+            self.head = (self.head + 1) % self.buf.len();
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Reader of `len` bytes from a circular buffer starting at `start`, wrapping at the end
+#[derive(Debug, Clone)]
+pub struct RingReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    left: usize,
+}
+
+impl<'a> RingReader<'a> {
+    pub fn new(buf: &'a [u8], start: usize, len: usize) -> Self {
+        let (pos, left) = if buf.is_empty() {
+            (0, 0)
+        } else {
+            (start % buf.len(), len)
+        };
+        Self { buf, pos, left }
+    }
+
+    /// Position of the next byte to be read
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Bytes left to read
+    pub fn remaining(&self) -> usize {
+        self.left
+    }
+}
+
+impl Read for RingReader<'_> {
+    fn read(&mut self, out: &mut [u8]) -> Result<usize> {
+        let n = out.len().min(self.left);
+        for b in &mut out[..n] {
+            *b = self.buf[self.pos];
+            self.pos = (self.pos + 1) % self.buf.len();
+        }
+        self.left -= n;
+        Ok(n)
+    }
+}
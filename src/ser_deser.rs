@@ -1,5 +1,7 @@
-use crate::{ReadVarint, Varint, VarintSizeHint, WriteVarint};
-use std::io::{Read, Result, Write};
+use crate::read_write::{read_encoded, write_encoded};
+use crate::{ReadVarint, Varint, VarintBaseType, VarintSizeHint, Varuint, WriteVarint};
+use std::io::{Error, ErrorKind, Read, Result, Write};
+use std::num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize};
 
 /// Trait for serializable types
 pub trait Serializable {
@@ -13,174 +15,412 @@ pub trait Serializable {
 pub trait Deserializable: Sized {
     /// Deserialize value from a `Read`
     fn deserialize(r: &mut dyn Read) -> Result<Self>;
-}
 
-impl Deserializable for Varint<u8> {
-    fn deserialize(r: &mut dyn Read) -> Result<Self> {
-        Ok(Self(r.read_varint()?))
+    /// Deserialize value from the beginning of `buf`, returns it along with the rest of `buf`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use varuint::{Deserializable, Varint};
+    ///
+    /// let (v, rest) = Varint::<u32>::deserialize_from_slice(&[241, 1, 0xaa]).unwrap();
+    /// assert_eq!(Varint(241), v);
+    /// assert_eq!([0xaa], rest);
+    /// ```
+    fn deserialize_from_slice(buf: &[u8]) -> Result<(Self, &[u8])> {
+        let mut rest = buf;
+        let v = Self::deserialize(&mut rest)?;
+        Ok((v, rest))
     }
 }
 
-impl Deserializable for Varint<u16> {
-    fn deserialize(r: &mut dyn Read) -> Result<Self> {
-        Ok(Self(r.read_varint()?))
+/// Implemented for every `VarintBaseType`, so generic code needs no extra bounds
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::{Deserializable, Serializable, Varint, VarintBaseType};
+///
+/// fn roundtrip<T: VarintBaseType>(v: T) -> T {
+///     let mut buf = Vec::new();
+///     Varint(v).serialize(&mut buf).unwrap();
+///     Varint::<T>::deserialize(&mut &buf[..]).unwrap().0
+/// }
+///
+/// assert_eq!(300u16, roundtrip(300u16));
+/// assert_eq!(-5isize, roundtrip(-5isize));
+/// ```
+impl<T: VarintBaseType> Serializable for Varint<T> {
+    #[inline]
+    fn size_hint(&self) -> usize {
+        self.0.varint_size()
     }
-}
 
-impl Deserializable for Varint<u32> {
-    fn deserialize(r: &mut dyn Read) -> Result<Self> {
-        Ok(Self(r.read_varint()?))
+    #[inline]
+    fn serialize(&self, w: &mut dyn Write) -> Result<usize> {
+        write_encoded(w, self.0)
     }
 }
 
-impl Deserializable for Varint<u64> {
+impl<T: VarintBaseType> Deserializable for Varint<T> {
     fn deserialize(r: &mut dyn Read) -> Result<Self> {
-        Ok(Self(r.read_varint()?))
+        Ok(Self(read_encoded(r)?))
     }
 }
 
-impl Deserializable for Varint<u128> {
-    fn deserialize(r: &mut dyn Read) -> Result<Self> {
-        Ok(Self(r.read_varint()?))
-    }
+macro_rules! impl_varuint {
+    ($($ty:ty),+) => {
+        $(
+            impl Serializable for Varuint<$ty> {
+                fn size_hint(&self) -> usize {
+                    self.0.varint_size()
+                }
+
+                fn serialize(&self, w: &mut dyn Write) -> Result<usize> {
+                    w.write_varint(self.0)
+                }
+            }
+
+            impl Deserializable for Varuint<$ty> {
+                fn deserialize(r: &mut dyn Read) -> Result<Self> {
+                    Ok(Self(r.read_varint()?))
+                }
+            }
+        )+
+    };
 }
 
-impl Serializable for Varint<u8> {
-    fn size_hint(&self) -> usize {
-        self.0.varint_size()
-    }
+impl_varuint!(u8, u16, u32, u64, u128, usize);
+
+/// Non-zero integers are encoded as `value - 1`, see `WriteVarint`
+macro_rules! impl_non_zero {
+    ($($nz:ty),+) => {
+        $(
+            impl Serializable for $nz {
+                fn size_hint(&self) -> usize {
+                    self.varint_size()
+                }
+
+                fn serialize(&self, w: &mut dyn Write) -> Result<usize> {
+                    w.write_varint(*self)
+                }
+            }
+
+            impl Deserializable for $nz {
+                fn deserialize(r: &mut dyn Read) -> Result<Self> {
+                    r.read_varint()
+                }
+            }
+        )+
+    };
+}
 
-    fn serialize(&self, w: &mut dyn Write) -> Result<usize> {
-        w.write_varint(self.0)
+impl_non_zero!(NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize);
+
+/// Upper bound of elements preallocated from an untrusted count
+const MAX_PREALLOC: u64 = 1024;
+
+/// Write `values.len()` (`Varint<u64>`) followed by each value, returns total bytes written
+pub fn write_varint_slice_prefixed<T: Copy>(w: &mut dyn Write, values: &[T]) -> Result<usize>
+where
+    for<'a> dyn Write + 'a: WriteVarint<T>,
+{
+    let mut size = WriteVarint::<u64>::write_varint(w, values.len() as u64)?;
+    for v in values {
+        size += w.write_varint(*v)?;
     }
+    Ok(size)
 }
 
-impl Serializable for Varint<u16> {
-    fn size_hint(&self) -> usize {
-        self.0.varint_size()
+/// Read values written by `write_varint_slice_prefixed`.
+/// Preallocation is capped, so a forged count can't trigger a huge allocation.
+pub fn read_varint_vec<T>(r: &mut dyn Read) -> Result<Vec<T>>
+where
+    for<'a> dyn Read + 'a: ReadVarint<T>,
+{
+    let count = ReadVarint::<u64>::read_varint(r)?;
+    let mut values = Vec::with_capacity(count.min(MAX_PREALLOC) as usize);
+    for _ in 0..count {
+        values.push(r.read_varint()?);
     }
+    Ok(values)
+}
 
-    fn serialize(&self, w: &mut dyn Write) -> Result<usize> {
-        w.write_varint(self.0)
-    }
+/// Write a nested message built by `f`, prefixed with its byte length (`Varint<u64>`).
+/// Returns total bytes written, including the prefix.
+pub fn write_nested<F>(w: &mut dyn Write, f: F) -> Result<usize>
+where
+    F: FnOnce(&mut Vec<u8>) -> Result<()>,
+{
+    let mut body = Vec::new();
+    f(&mut body)?;
+    let size = w.write_varint(body.len() as u64)?;
+    w.write_all(&body)?;
+    Ok(size + body.len())
 }
 
-impl Serializable for Varint<u32> {
-    fn size_hint(&self) -> usize {
-        self.0.varint_size()
-    }
+/// Write `data.len()` (`Varint<u64>`) followed by the raw bytes, returns total bytes written
+pub fn write_length_delimited(w: &mut dyn Write, data: &[u8]) -> Result<usize> {
+    let size = w.write_varint(data.len() as u64)?;
+    w.write_all(data)?;
+    Ok(size + data.len())
+}
 
-    fn serialize(&self, w: &mut dyn Write) -> Result<usize> {
-        w.write_varint(self.0)
+/// Read bytes written by `write_length_delimited`.
+/// Fails with `InvalidData` if the length exceeds `max_len`, preallocation is capped as well.
+pub fn read_length_delimited(r: &mut dyn Read, max_len: usize) -> Result<Vec<u8>> {
+    let len: u64 = r.read_varint()?;
+    let len = match usize::try_from(len) {
+        Ok(len) if len <= max_len => len,
+        _ => return Err(Error::from(ErrorKind::InvalidData)),
+    };
+    let mut data = Vec::with_capacity(len.min(MAX_PREALLOC as usize));
+    r.take(len as u64).read_to_end(&mut data)?;
+    if data.len() != len {
+        return Err(Error::from(ErrorKind::UnexpectedEof));
     }
+    Ok(data)
 }
 
-impl Serializable for Varint<u64> {
-    fn size_hint(&self) -> usize {
-        self.0.varint_size()
-    }
+/// Write `s` as its UTF-8 byte length (`Varint<u64>`) followed by the bytes,
+/// returns total bytes written
+pub fn write_string(w: &mut dyn Write, s: &str) -> Result<usize> {
+    write_length_delimited(w, s.as_bytes())
+}
 
-    fn serialize(&self, w: &mut dyn Write) -> Result<usize> {
-        w.write_varint(self.0)
-    }
+/// Read a string written by `write_string`.
+/// Fails with `InvalidData` if the byte length exceeds `max_len` or the bytes are not UTF-8.
+pub fn read_string(r: &mut dyn Read, max_len: usize) -> Result<String> {
+    let bytes = read_length_delimited(r, max_len)?;
+    String::from_utf8(bytes).map_err(|e| Error::new(ErrorKind::InvalidData, e))
 }
 
-impl Serializable for Varint<u128> {
-    fn size_hint(&self) -> usize {
-        self.0.varint_size()
-    }
+/// Write booleans as the count (`Varint<u64>`) followed by bits packed 8 per byte, LSB first.
+/// Unused bits of the last byte are zero. Returns bytes written.
+pub fn write_bool_vec(w: &mut dyn Write, bits: &[bool]) -> Result<usize> {
+    let size = w.write_varint(bits.len() as u64)?;
+    let packed: Vec<u8> = bits
+        .chunks(8)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .enumerate()
+                .fold(0u8, |byte, (i, &bit)| byte | (u8::from(bit) << i))
+        })
+        .collect();
+    w.write_all(&packed)?;
+    Ok(size + packed.len())
+}
 
-    fn serialize(&self, w: &mut dyn Write) -> Result<usize> {
-        w.write_varint(self.0)
+/// Read booleans written by `write_bool_vec`, unused bits of the last byte are ignored.
+/// Fails with `InvalidData` if the count exceeds `max_count`.
+pub fn read_bool_vec(r: &mut dyn Read, max_count: usize) -> Result<Vec<bool>> {
+    let count: u64 = r.read_varint()?;
+    let count = match usize::try_from(count) {
+        Ok(count) if count <= max_count => count,
+        _ => return Err(Error::from(ErrorKind::InvalidData)),
+    };
+    let mut packed = vec![0u8; count.div_ceil(8)];
+    r.read_exact(&mut packed)?;
+    Ok((0..count)
+        .map(|i| packed[i / 8] & (1 << (i % 8)) != 0)
+        .collect())
+}
+
+/// Write a tag byte followed by the value: `1` and zigzag `Varint<i128>` for signed values,
+/// `0` and plain `Varint<u128>` for unsigned ones. Returns bytes written.
+/// Fails with `InvalidInput` for a negative value marked as unsigned.
+pub fn write_tagged_sign(w: &mut dyn Write, value: i128, is_signed: bool) -> Result<usize> {
+    if is_signed {
+        w.write_all(&[1])?;
+        return Ok(1 + w.write_varint(value)?);
     }
+    let value = u128::try_from(value).map_err(|_| Error::from(ErrorKind::InvalidInput))?;
+    w.write_all(&[0])?;
+    Ok(1 + w.write_varint(value)?)
 }
 
-impl Serializable for Varint<i8> {
-    #[inline]
-    fn size_hint(&self) -> usize {
-        self.0.varint_size()
+/// Read a value written by `write_tagged_sign`, returns the value and whether it was signed.
+/// Fails with `InvalidData` on an unknown tag or an unsigned value above `i128::MAX`.
+pub fn read_tagged_sign(r: &mut dyn Read) -> Result<(i128, bool)> {
+    let mut tag = [0u8; 1];
+    r.read_exact(&mut tag)?;
+    match tag[0] {
+        0 => {
+            let v: u128 = r.read_varint()?;
+            let v = i128::try_from(v).map_err(|_| Error::from(ErrorKind::InvalidData))?;
+            Ok((v, false))
+        }
+        1 => Ok((r.read_varint()?, true)),
+        _ => Err(Error::from(ErrorKind::InvalidData)),
     }
+}
+
+/// Trait for types that know their serialized byte-length without serializing
+pub trait VarintLen {
+    /// Exact number of bytes `serialize` writes for the value
+    fn encoded_len(&self) -> usize;
+}
 
+impl<T: VarintBaseType> VarintLen for T {
     #[inline]
-    fn serialize(&self, w: &mut dyn Write) -> Result<usize> {
-        w.write_varint(self.0)
+    fn encoded_len(&self) -> usize {
+        self.varint_size()
     }
 }
 
-impl Deserializable for Varint<i8> {
-    fn deserialize(r: &mut dyn Read) -> Result<Self> {
-        Ok(Self(r.read_varint()?))
+impl<T: VarintBaseType> VarintLen for Varint<T> {
+    #[inline]
+    fn encoded_len(&self) -> usize {
+        self.0.varint_size()
     }
 }
 
-impl Serializable for Varint<i16> {
+impl<T: VarintBaseType> VarintLen for Varuint<T> {
     #[inline]
-    fn size_hint(&self) -> usize {
+    fn encoded_len(&self) -> usize {
         self.0.varint_size()
     }
+}
 
-    #[inline]
-    fn serialize(&self, w: &mut dyn Write) -> Result<usize> {
-        w.write_varint(self.0)
+impl<T: VarintLen> VarintLen for Option<T> {
+    fn encoded_len(&self) -> usize {
+        1 + self.as_ref().map_or(0, VarintLen::encoded_len)
     }
 }
 
-impl Deserializable for Varint<i16> {
-    fn deserialize(r: &mut dyn Read) -> Result<Self> {
-        Ok(Self(r.read_varint()?))
+impl<T: VarintLen> VarintLen for Vec<T> {
+    fn encoded_len(&self) -> usize {
+        (self.len() as u64).varint_size() + self.iter().map(VarintLen::encoded_len).sum::<usize>()
     }
 }
 
-impl Serializable for Varint<i32> {
-    #[inline]
+macro_rules! impl_varint_len_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: VarintLen),+> VarintLen for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn encoded_len(&self) -> usize {
+                let ($($name,)+) = self;
+                0 $(+ $name.encoded_len())+
+            }
+        }
+    };
+}
+
+impl_varint_len_tuple!(A, B);
+impl_varint_len_tuple!(A, B, C);
+impl_varint_len_tuple!(A, B, C, D);
+
+/// `Option` is encoded as a `0` or `1` presence byte followed by the value if present
+impl<T: Serializable> Serializable for Option<T> {
     fn size_hint(&self) -> usize {
-        self.0.varint_size()
+        1 + self.as_ref().map_or(0, Serializable::size_hint)
     }
 
-    #[inline]
     fn serialize(&self, w: &mut dyn Write) -> Result<usize> {
-        w.write_varint(self.0)
+        match self {
+            None => {
+                w.write_all(&[0])?;
+                Ok(1)
+            }
+            Some(v) => {
+                w.write_all(&[1])?;
+                Ok(1 + v.serialize(w)?)
+            }
+        }
     }
 }
 
-impl Deserializable for Varint<i32> {
+impl<T: Deserializable> Deserializable for Option<T> {
     fn deserialize(r: &mut dyn Read) -> Result<Self> {
-        Ok(Self(r.read_varint()?))
+        let mut tag = [0u8; 1];
+        r.read_exact(&mut tag)?;
+        match tag[0] {
+            0 => Ok(None),
+            1 => Ok(Some(T::deserialize(r)?)),
+            _ => Err(Error::from(ErrorKind::InvalidData)),
+        }
     }
 }
 
-impl Serializable for Varint<i64> {
-    #[inline]
+/// `Vec` is encoded as the element count (`Varint<u64>`) followed by the elements
+impl<T: Serializable> Serializable for Vec<T> {
     fn size_hint(&self) -> usize {
-        self.0.varint_size()
+        (self.len() as u64).varint_size() + self.iter().map(Serializable::size_hint).sum::<usize>()
     }
 
-    #[inline]
     fn serialize(&self, w: &mut dyn Write) -> Result<usize> {
-        w.write_varint(self.0)
+        let mut size = w.write_varint(self.len() as u64)?;
+        for v in self {
+            size += v.serialize(w)?;
+        }
+        Ok(size)
     }
 }
 
-impl Deserializable for Varint<i64> {
+impl<T: Deserializable> Deserializable for Vec<T> {
     fn deserialize(r: &mut dyn Read) -> Result<Self> {
-        Ok(Self(r.read_varint()?))
+        let count: u64 = r.read_varint()?;
+        // Do not trust the count for preallocation, every element takes at least a byte anyway
+        let mut values = Vec::with_capacity(count.min(MAX_PREALLOC) as usize);
+        for _ in 0..count {
+            values.push(T::deserialize(r)?);
+        }
+        Ok(values)
     }
 }
 
-impl Serializable for Varint<i128> {
-    #[inline]
+macro_rules! impl_ser_deser_tuple {
+    ($($name:ident),+) => {
+        /// Tuples are encoded as their elements in order, without any prefix
+        impl<$($name: Serializable),+> Serializable for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn size_hint(&self) -> usize {
+                let ($($name,)+) = self;
+                0 $(+ $name.size_hint())+
+            }
+
+            #[allow(non_snake_case)]
+            fn serialize(&self, w: &mut dyn Write) -> Result<usize> {
+                let ($($name,)+) = self;
+                Ok(0 $(+ $name.serialize(w)?)+)
+            }
+        }
+
+        impl<$($name: Deserializable),+> Deserializable for ($($name,)+) {
+            fn deserialize(r: &mut dyn Read) -> Result<Self> {
+                Ok(($($name::deserialize(r)?,)+))
+            }
+        }
+    };
+}
+
+impl_ser_deser_tuple!(A, B);
+impl_ser_deser_tuple!(A, B, C);
+impl_ser_deser_tuple!(A, B, C, D);
+
+/// Arrays are encoded as their elements in order, the length is not written
+impl<T: Serializable, const N: usize> Serializable for [T; N] {
     fn size_hint(&self) -> usize {
-        self.0.varint_size()
+        self.iter().map(Serializable::size_hint).sum()
     }
 
-    #[inline]
     fn serialize(&self, w: &mut dyn Write) -> Result<usize> {
-        w.write_varint(self.0)
+        let mut size = 0;
+        for v in self {
+            size += v.serialize(w)?;
+        }
+        Ok(size)
     }
 }
 
-impl Deserializable for Varint<i128> {
+/// Stops at the first element failing to deserialize
+impl<T: Deserializable, const N: usize> Deserializable for [T; N] {
     fn deserialize(r: &mut dyn Read) -> Result<Self> {
-        Ok(Self(r.read_varint()?))
+        let mut values: [Option<T>; N] = std::array::from_fn(|_| None);
+        for v in &mut values {
+            *v = Some(T::deserialize(r)?);
+        }
+        Ok(values.map(|v| v.expect("every element is deserialized")))
     }
 }
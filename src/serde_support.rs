@@ -0,0 +1,88 @@
+//! Serde support, enabled by the `serde-support` feature.
+//!
+//! Binary formats get the varint encoded bytes through `serialize_bytes`,
+//! human-readable ones get the plain inner integer.
+use crate::{Varint, VarintBaseType, VarintCodec, Varuint, MAX_VARINT_LEN};
+use core::{fmt, marker::PhantomData};
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+impl<T: VarintBaseType + Serialize> Serialize for Varint<T> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        serialize(self.0, s)
+    }
+}
+
+impl<'de, T: VarintBaseType + Deserialize<'de>> Deserialize<'de> for Varint<T> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        deserialize(d).map(Self)
+    }
+}
+
+impl<T: VarintBaseType + Serialize> Serialize for Varuint<T> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        serialize(self.0, s)
+    }
+}
+
+impl<'de, T: VarintBaseType + Deserialize<'de>> Deserialize<'de> for Varuint<T> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        deserialize(d).map(Self)
+    }
+}
+
+fn serialize<T, S>(v: T, s: S) -> Result<S::Ok, S::Error>
+where
+    T: VarintBaseType + Serialize,
+    S: Serializer,
+{
+    if s.is_human_readable() {
+        return v.serialize(s);
+    }
+    let mut buf = [0u8; MAX_VARINT_LEN];
+    let len = v.encode_to_slice(&mut buf);
+    s.serialize_bytes(&buf[..len])
+}
+
+fn deserialize<'de, T, D>(d: D) -> Result<T, D::Error>
+where
+    T: VarintBaseType + Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    if d.is_human_readable() {
+        return T::deserialize(d);
+    }
+    d.deserialize_bytes(BytesVisitor(PhantomData))
+}
+
+struct BytesVisitor<T>(PhantomData<T>);
+
+impl<'de, T: VarintCodec> Visitor<'de> for BytesVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "varint encoded bytes")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<T, E> {
+        match T::decode_from_slice(v) {
+            Ok((value, len)) if len == v.len() => Ok(value),
+            Ok(_) => Err(E::invalid_length(v.len(), &self)),
+            Err(e) => Err(E::custom(e)),
+        }
+    }
+
+    // formats without a native byte string encode bytes as a sequence
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+        let mut buf = [0u8; MAX_VARINT_LEN];
+        let mut len = 0;
+        while let Some(b) = seq.next_element()? {
+            if len == buf.len() {
+                return Err(de::Error::invalid_length(len + 1, &self));
+            }
+            buf[len] = b;
+            len += 1;
+        }
+        self.visit_bytes(&buf[..len])
+    }
+}
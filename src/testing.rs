@@ -0,0 +1,9 @@
+//! Helpers for testing code built on the crate, enabled by the `test-utils` feature.
+use crate::{Varint, VarintBaseType};
+use std::{fmt, str::FromStr};
+
+/// Check that `Varint(v)` survives a `Display` to `FromStr` round trip
+pub fn check_str_roundtrip<T: VarintBaseType + fmt::Display + FromStr>(v: T) -> bool {
+    let v = Varint(v);
+    v.to_string().parse::<Varint<T>>().is_ok_and(|p| p == v)
+}
@@ -0,0 +1,68 @@
+use crate::{VarintCodec, VarintError};
+use core::ops::Deref;
+
+/// Value decoded from a canonical, in-range encoding, along with the encoded byte-length.
+///
+/// Can only be obtained through `decode` or from a plain value, so code receiving it doesn't
+/// need to re-check externally supplied bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::{ValidatedVarint, VarintError};
+///
+/// let v = ValidatedVarint::<u32>::decode(&[241, 1, 0xff]).unwrap();
+/// assert_eq!(241, *v);
+/// assert_eq!(2, v.byte_len());
+/// assert_eq!(
+///     Err(VarintError::NonCanonical),
+///     ValidatedVarint::<u32>::decode(&[249, 5, 0, 0])
+/// );
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub struct ValidatedVarint<T> {
+    value: T,
+    len: usize,
+}
+
+impl<T: VarintCodec + Copy> ValidatedVarint<T> {
+    /// Decode from the beginning of `buf`.
+    /// Fails with `NonCanonical` if the value is not encoded in the minimal number of bytes.
+    pub fn decode(buf: &[u8]) -> Result<Self, VarintError> {
+        let (value, len) = T::decode_from_slice(buf)?;
+        if len != value.varint_size() {
+            return Err(VarintError::NonCanonical);
+        }
+        Ok(Self { value, len })
+    }
+
+    /// Encoded byte-length of the value
+    #[inline]
+    pub fn byte_len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: VarintCodec + Copy> From<T> for ValidatedVarint<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self {
+            value,
+            len: value.varint_size(),
+        }
+    }
+}
+
+impl<T> Deref for ValidatedVarint<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
@@ -1,15 +1,47 @@
-use crate::VarintSizeHint;
-use std::{
+#[cfg(feature = "std")]
+use crate::Serializable;
+use crate::{VarintBytes, VarintCodec, VarintError, VarintSizeHint};
+use core::{
+    convert::TryFrom,
     fmt,
-    ops::{Deref, DerefMut},
+    hash::{Hash, Hasher},
+    iter::{Product, Sum},
+    num::{Saturating, TryFromIntError, Wrapping},
+    ops::{Add, AddAssign, Deref, DerefMut, Sub, SubAssign},
+    str::FromStr,
 };
 
+/// Integer types usable as `Varint`/`Varuint` storage.
+///
+/// Signedness and bit width are available as `T::SIGNED` and `T::BITS` from `VarintCodec`.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::VarintBaseType;
+///
+/// fn describe<T: VarintBaseType>() -> (bool, u32) {
+///     (T::SIGNED, T::BITS)
+/// }
+///
+/// assert_eq!((true, 16), describe::<i16>());
+/// assert_eq!((false, 128), describe::<u128>());
+/// ```
 pub trait VarintBaseType:
-    Copy + Clone + PartialEq + PartialOrd + Eq + Ord + fmt::Debug + VarintSizeHint
+    Copy + Clone + PartialEq + PartialOrd + Eq + Ord + fmt::Debug + VarintSizeHint + VarintCodec
 {
 }
-impl<T: Copy + Clone + PartialEq + PartialOrd + Eq + Ord + fmt::Debug + VarintSizeHint>
-    VarintBaseType for T
+impl<
+        T: Copy
+            + Clone
+            + PartialEq
+            + PartialOrd
+            + Eq
+            + Ord
+            + fmt::Debug
+            + VarintSizeHint
+            + VarintCodec,
+    > VarintBaseType for T
 {
 }
 
@@ -18,15 +50,13 @@ impl<T: Copy + Clone + PartialEq + PartialOrd + Eq + Ord + fmt::Debug + VarintSi
 /// # Examples
 ///
 /// ```rust
-/// use std::mem;
-///
 /// use varuint::{Varint, Serializable, Deserializable};
 ///
 /// fn test_varint(v: i128, size: usize) {
 ///     println!("{}", v);
 ///     let v = Varint(v);
 ///     assert_eq!(size, v.size_hint());
-///     let mut arr: [u8; 17] = unsafe { mem::uninitialized() };
+///     let mut arr = [0u8; 17];
 ///     assert_eq!(size, v.serialize(&mut (&mut arr as &mut [u8])).unwrap());
 ///     assert_eq!(v, Varint::deserialize(&mut (&arr as &[u8])).unwrap());
 /// }
@@ -65,10 +95,126 @@ impl<T: Copy + Clone + PartialEq + PartialOrd + Eq + Ord + fmt::Debug + VarintSi
 /// test_varint(i128::min_value(), 17);
 /// test_varint(i128::max_value(), 17);
 /// ```
-#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Copy, Clone)]
-#[cfg_attr(feature = "serde-support", derive(Serialize, Deserialize))]
+///
+/// `Hash` is computed over the encoded bytes, so the same value hashes the same
+/// regardless of the integer width: `Varint(5u32)` and `Varint(5u64)` hash identically.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Copy, Clone)]
 pub struct Varint<T: VarintBaseType>(pub T);
 
+/// Feed the canonical encoding of `v` to `state`
+#[inline]
+pub(crate) fn hash_encoded<T: VarintBaseType, H: Hasher>(v: T, state: &mut H) {
+    // encodings are prefix-free, so no length is needed to keep composite hashes unambiguous
+    state.write(&VarintBytes::new(v));
+}
+
+impl<T: VarintBaseType> Hash for Varint<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_encoded(self.0, state);
+    }
+}
+
+impl<T: VarintBaseType> Varint<T> {
+    /// Maximum encoded byte-length of `Varint<T>`
+    pub const MAX_ENCODED_LEN: usize = T::MAX_ENCODED_LEN;
+
+    /// Encoded byte-length of the value
+    #[inline]
+    pub fn encoded_len(&self) -> usize {
+        self.0.varint_size()
+    }
+
+    /// Whether the value is encoded to a single byte
+    #[inline]
+    pub fn is_single_byte(&self) -> bool {
+        self.encoded_len() == 1
+    }
+
+    /// Whether the encoded value fits in `remaining` bytes, computed without encoding.
+    /// In const contexts compare `varint_size_u64` and friends to `remaining` instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use varuint::Varint;
+    ///
+    /// let mut remaining = 4;
+    /// for v in [Varint(5u64), Varint(300), Varint(70_000)] {
+    ///     if v.fits_in(remaining) {
+    ///         remaining -= v.encoded_len();
+    ///     }
+    /// }
+    /// assert_eq!(1, remaining);
+    /// ```
+    #[inline]
+    pub fn fits_in(&self, remaining: usize) -> bool {
+        self.encoded_len() <= remaining
+    }
+
+    /// Encode to the beginning of `buf` without `std::io`, returns the number of bytes written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buf` is shorter than `encoded_len()`.
+    #[inline]
+    pub fn encode_into(&self, buf: &mut [u8]) -> usize {
+        self.0.encode_to_slice(buf)
+    }
+
+    /// Encoded bytes of the value, stored inline without allocating
+    #[inline]
+    pub fn encode(&self) -> VarintBytes {
+        VarintBytes::new(self.0)
+    }
+
+    /// Decode from the beginning of `buf` without `std::io`,
+    /// returns the value and the number of bytes consumed
+    #[inline]
+    pub fn decode_from(buf: &[u8]) -> Result<(T, usize), VarintError> {
+        T::decode_from_slice(buf)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: VarintBaseType> Varint<T> {
+    /// Encoded bytes of the value
+    #[inline]
+    pub fn to_vec(&self) -> Vec<u8> {
+        crate::to_varint_vec(self.0)
+    }
+
+    /// Reader yielding the encoded bytes of the value, encoded once into an inline buffer
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use varuint::Varint;
+    ///
+    /// let mut out = Vec::new();
+    /// std::io::copy(&mut Varint(300u32).reader(), &mut out).unwrap();
+    /// assert_eq!(vec![241, 60], out);
+    /// ```
+    #[inline]
+    pub fn reader(self) -> impl std::io::Read {
+        std::io::Cursor::new(self.encode())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: VarintBaseType> Varint<T>
+where
+    Self: Serializable,
+{
+    /// Encoded bytes formatted as a Rust array literal, e.g. `[0xf1, 0x01]`
+    pub fn to_rust_literal(&self) -> String {
+        let mut buf = Vec::with_capacity(self.size_hint());
+        self.serialize(&mut buf).expect("writing to Vec never fails");
+        let bytes: Vec<String> = buf.iter().map(|b| format!("{:#04x}", b)).collect();
+        format!("[{}]", bytes.join(", "))
+    }
+}
+
 impl<T: VarintBaseType + fmt::Display> fmt::Display for Varint<T> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -76,6 +222,15 @@ impl<T: VarintBaseType + fmt::Display> fmt::Display for Varint<T> {
     }
 }
 
+impl<T: VarintBaseType + FromStr> FromStr for Varint<T> {
+    type Err = T::Err;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self)
+    }
+}
+
 impl<T: VarintBaseType> Deref for Varint<T> {
     type Target = T;
 
@@ -92,6 +247,68 @@ impl<T: VarintBaseType> DerefMut for Varint<T> {
     }
 }
 
+impl<T: VarintBaseType + Add<Output = T>> Add for Varint<T> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl<T: VarintBaseType + Sub<Output = T>> Sub for Varint<T> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl<T: VarintBaseType + AddAssign> AddAssign for Varint<T> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl<T: VarintBaseType + SubAssign> SubAssign for Varint<T> {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+/// Overflows the same way as `Sum` of the underlying type
+impl<T: VarintBaseType + Sum> Sum for Varint<T> {
+    #[inline]
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        Self(iter.map(|v| v.0).sum())
+    }
+}
+
+impl<'a, T: VarintBaseType + Sum> Sum<&'a Varint<T>> for Varint<T> {
+    #[inline]
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+/// Overflows the same way as `Product` of the underlying type
+impl<T: VarintBaseType + Product> Product for Varint<T> {
+    #[inline]
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        Self(iter.map(|v| v.0).product())
+    }
+}
+
+impl<'a, T: VarintBaseType + Product> Product<&'a Varint<T>> for Varint<T> {
+    #[inline]
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().product()
+    }
+}
+
 impl From<i8> for Varint<i8> {
     #[inline]
     fn from(i: i8) -> Self {
@@ -232,6 +449,58 @@ impl From<u128> for Varint<u128> {
     }
 }
 
+impl<T: VarintBaseType> From<Wrapping<T>> for Varint<T> {
+    #[inline]
+    fn from(v: Wrapping<T>) -> Self {
+        Self(v.0)
+    }
+}
+
+impl<T: VarintBaseType> From<Varint<T>> for Wrapping<T> {
+    #[inline]
+    fn from(v: Varint<T>) -> Self {
+        Wrapping(v.0)
+    }
+}
+
+impl<T: VarintBaseType> From<Saturating<T>> for Varint<T> {
+    #[inline]
+    fn from(v: Saturating<T>) -> Self {
+        Self(v.0)
+    }
+}
+
+impl<T: VarintBaseType> From<Varint<T>> for Saturating<T> {
+    #[inline]
+    fn from(v: Varint<T>) -> Self {
+        Saturating(v.0)
+    }
+}
+
+macro_rules! impl_try_from_narrowing {
+    ($from:ty => $($to:ty),+) => {
+        $(
+            impl TryFrom<Varint<$from>> for Varint<$to> {
+                type Error = TryFromIntError;
+
+                #[inline]
+                fn try_from(v: Varint<$from>) -> Result<Self, Self::Error> {
+                    <$to>::try_from(v.0).map(Self)
+                }
+            }
+        )+
+    };
+}
+
+impl_try_from_narrowing!(u16 => u8);
+impl_try_from_narrowing!(u32 => u8, u16);
+impl_try_from_narrowing!(u64 => u8, u16, u32);
+impl_try_from_narrowing!(u128 => u8, u16, u32, u64);
+impl_try_from_narrowing!(i16 => i8);
+impl_try_from_narrowing!(i32 => i8, i16);
+impl_try_from_narrowing!(i64 => i8, i16, i32);
+impl_try_from_narrowing!(i128 => i8, i16, i32, i64);
+
 impl Default for Varint<u128> {
     #[inline]
     fn default() -> Self {
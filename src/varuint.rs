@@ -0,0 +1,118 @@
+use crate::varint::hash_encoded;
+use crate::{VarintBaseType, VarintBytes};
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Deref, DerefMut},
+    str::FromStr,
+};
+
+/// Variable length unsigned integer.
+///
+/// Encoded the same way as `Varint` over an unsigned type, but serialization is implemented for
+/// unsigned types only, so zigzag encoding is never applied.
+///
+/// # Examples
+///
+/// ```rust
+/// use varuint::{Varuint, Serializable, Deserializable};
+///
+/// let v = Varuint::<u64>::from(300u16);
+/// let mut buf = Vec::new();
+/// assert_eq!(2, v.serialize(&mut buf).unwrap());
+/// assert_eq!(v, Varuint::deserialize(&mut &buf[..]).unwrap());
+/// ```
+///
+/// Like `Varint`, `Hash` is computed over the encoded bytes.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Copy, Clone)]
+pub struct Varuint<T: VarintBaseType>(pub T);
+
+impl<T: VarintBaseType> Hash for Varuint<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_encoded(self.0, state);
+    }
+}
+
+impl<T: VarintBaseType> Varuint<T> {
+    /// Maximum encoded byte-length of `Varuint<T>`
+    pub const MAX_ENCODED_LEN: usize = T::MAX_ENCODED_LEN;
+
+    /// Encoded byte-length of the value
+    #[inline]
+    pub fn encoded_len(&self) -> usize {
+        self.0.varint_size()
+    }
+
+    /// Encoded bytes of the value, stored inline without allocating
+    #[inline]
+    pub fn encode(&self) -> VarintBytes {
+        VarintBytes::new(self.0)
+    }
+}
+
+impl<T: VarintBaseType + fmt::Display> fmt::Display for Varuint<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<T: VarintBaseType + FromStr> FromStr for Varuint<T> {
+    type Err = T::Err;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self)
+    }
+}
+
+impl<T: VarintBaseType> Deref for Varuint<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: VarintBaseType> DerefMut for Varuint<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+macro_rules! impl_from {
+    ($from:ty => $($to:ty),+) => {
+        $(
+            impl From<$from> for Varuint<$to> {
+                #[inline]
+                fn from(v: $from) -> Self {
+                    Self(<$to>::from(v))
+                }
+            }
+        )+
+    };
+}
+
+impl_from!(u8 => u8, u16, u32, u64, u128);
+impl_from!(u16 => u16, u32, u64, u128);
+impl_from!(u32 => u32, u64, u128);
+impl_from!(u64 => u64, u128);
+impl_from!(u128 => u128);
+
+macro_rules! impl_default {
+    ($($ty:ty),+) => {
+        $(
+            impl Default for Varuint<$ty> {
+                #[inline]
+                fn default() -> Self {
+                    Self(0)
+                }
+            }
+        )+
+    };
+}
+
+impl_default!(u8, u16, u32, u64, u128);
@@ -0,0 +1,83 @@
+//! ZigZag mapping between signed and unsigned integers.
+//!
+//! This is the same scheme as Protobuf's `sint32`/`sint64`: values closer to zero map to smaller
+//! unsigned numbers, `0 -> 0`, `-1 -> 1`, `1 -> 2`, `-2 -> 3` and so on. `Varint` applies it to
+//! every signed value before encoding. The per-width `varint_to_varuint_*`/`varuint_to_varint_*`
+//! functions do the same and are usable in const contexts.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use varuint::zigzag;
+//!
+//! assert_eq!(1u32, zigzag::encode(-1i32));
+//! assert_eq!(2u32, zigzag::encode(1i32));
+//! assert_eq!(u8::MAX, zigzag::encode(i8::MIN));
+//! assert_eq!(-1i64, zigzag::decode(1u64));
+//! ```
+use crate::read_write::{
+    varint_to_varuint_128, varint_to_varuint_16, varint_to_varuint_32, varint_to_varuint_64,
+    varint_to_varuint_8, varuint_to_varint_128, varuint_to_varint_16, varuint_to_varint_32,
+    varuint_to_varint_64, varuint_to_varint_8,
+};
+
+/// Signed integer with a ZigZag mapping to the unsigned integer of the same width
+pub trait ZigZag: Copy {
+    /// Unsigned counterpart
+    type Unsigned: Copy;
+
+    fn zigzag_encode(self) -> Self::Unsigned;
+
+    fn zigzag_decode(v: Self::Unsigned) -> Self;
+}
+
+macro_rules! impl_zigzag {
+    ($ty:ty, $uty:ty, $enc:expr, $dec:expr) => {
+        impl ZigZag for $ty {
+            type Unsigned = $uty;
+
+            #[inline(always)]
+            fn zigzag_encode(self) -> $uty {
+                $enc(self)
+            }
+
+            #[inline(always)]
+            fn zigzag_decode(v: $uty) -> Self {
+                $dec(v)
+            }
+        }
+    };
+}
+
+impl_zigzag!(i8, u8, varint_to_varuint_8, varuint_to_varint_8);
+impl_zigzag!(i16, u16, varint_to_varuint_16, varuint_to_varint_16);
+impl_zigzag!(i32, u32, varint_to_varuint_32, varuint_to_varint_32);
+impl_zigzag!(i64, u64, varint_to_varuint_64, varuint_to_varint_64);
+impl_zigzag!(i128, u128, varint_to_varuint_128, varuint_to_varint_128);
+
+// mapped through `i64`, the ZigZag form of a value that fits `isize` always fits `usize`
+impl ZigZag for isize {
+    type Unsigned = usize;
+
+    #[inline(always)]
+    fn zigzag_encode(self) -> usize {
+        varint_to_varuint_64(self as i64) as usize
+    }
+
+    #[inline(always)]
+    fn zigzag_decode(v: usize) -> Self {
+        varuint_to_varint_64(v as u64) as isize
+    }
+}
+
+/// Map a signed value to its unsigned ZigZag form
+#[inline(always)]
+pub fn encode<T: ZigZag>(v: T) -> T::Unsigned {
+    v.zigzag_encode()
+}
+
+/// Map an unsigned ZigZag form back to the signed value
+#[inline(always)]
+pub fn decode<T: ZigZag>(v: T::Unsigned) -> T {
+    T::zigzag_decode(v)
+}
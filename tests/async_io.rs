@@ -0,0 +1,100 @@
+#![cfg(feature = "tokio")]
+
+use std::io::ErrorKind;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use varuint::{peek_varint, read_varint_async, write_varint_async, VarintPeek, WriteVarint};
+
+#[tokio::test]
+async fn test_peek_varint() {
+    let data: &[u8] = &[249, 0xf0, 0x07, 0x01, 5];
+    let mut r = BufReader::new(data);
+    assert_eq!(
+        VarintPeek::Ready {
+            value: 67568u32,
+            len: 4
+        },
+        peek_varint(&mut r).await.unwrap()
+    );
+    assert_eq!(
+        VarintPeek::Ready {
+            value: 67568u32,
+            len: 4
+        },
+        peek_varint(&mut r).await.unwrap()
+    );
+    r.consume(4);
+    assert_eq!(
+        VarintPeek::Ready { value: 5u8, len: 1 },
+        peek_varint(&mut r).await.unwrap()
+    );
+    r.consume(1);
+    assert_eq!(
+        ErrorKind::UnexpectedEof,
+        peek_varint::<u8, _>(&mut r).await.unwrap_err().kind()
+    );
+
+    let mut r = BufReader::with_capacity(2, data);
+    assert_eq!(
+        VarintPeek::Incomplete,
+        peek_varint::<u32, _>(&mut r).await.unwrap()
+    );
+
+    // a value straddling the end of the `BufReader` buffer is never completed
+    let data: &[u8] = &[5, 249, 0xf0, 0x07, 0x01];
+    let mut r = BufReader::with_capacity(3, data);
+    assert_eq!(
+        VarintPeek::Ready { value: 5u8, len: 1 },
+        peek_varint(&mut r).await.unwrap()
+    );
+    r.consume(1);
+    for _ in 0..3 {
+        assert_eq!(
+            VarintPeek::Incomplete,
+            peek_varint::<u32, _>(&mut r).await.unwrap()
+        );
+    }
+    assert_eq!(67568u32, read_varint_async(&mut r).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_read_write_async() {
+    let values = [0i64, -1, 120, -2000, 70_000, i64::MIN, i64::MAX];
+    let mut expected = Vec::new();
+    for v in values {
+        expected.write_varint(v).unwrap();
+    }
+
+    // one-byte pipe forces every multi-byte value to arrive across separate reads
+    let (mut tx, mut rx) = tokio::io::duplex(1);
+    let writer = async {
+        let mut total = 0;
+        for v in values {
+            total += write_varint_async(&mut tx, v).await.unwrap();
+        }
+        total
+    };
+    let reader = async {
+        let mut out = Vec::new();
+        for _ in values {
+            out.push(read_varint_async::<i64, _>(&mut rx).await.unwrap());
+        }
+        out
+    };
+    let (total, out) = tokio::join!(writer, reader);
+    assert_eq!(expected.len(), total);
+    assert_eq!(values[..], out[..]);
+
+    let mut r: &[u8] = &[248, 0];
+    assert_eq!(
+        ErrorKind::InvalidData,
+        read_varint_async::<u8, _>(&mut r).await.unwrap_err().kind()
+    );
+    let mut r: &[u8] = &[249, 0];
+    assert_eq!(
+        ErrorKind::UnexpectedEof,
+        read_varint_async::<u32, _>(&mut r)
+            .await
+            .unwrap_err()
+            .kind()
+    );
+}
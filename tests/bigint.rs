@@ -0,0 +1,95 @@
+#![cfg(feature = "bigint")]
+
+use num_bigint::BigUint;
+use std::io::{Cursor, ErrorKind};
+use varuint::{ReadVarint, ReadVarintBig, WriteVarint, WriteVarintBig};
+
+fn test_big(v: BigUint, size: usize) {
+    let mut cur = Cursor::new(Vec::new());
+    assert_eq!(size, cur.write_varint_big(&v).unwrap());
+    assert_eq!(size, cur.position() as usize);
+    cur.set_position(0);
+    assert_eq!(v, cur.read_varint_big().unwrap());
+}
+
+#[test]
+fn test_all() {
+    test_big(BigUint::from(0u8), 1);
+    test_big(BigUint::from(2031u16), 2);
+    test_big(BigUint::from(u64::MAX), 9);
+    test_big(BigUint::from(u64::MAX) + 1u8, 17);
+    test_big(BigUint::from(u128::MAX), 17);
+    test_big(BigUint::from(u128::MAX) + 1u8, 20);
+    test_big(BigUint::from(u128::MAX) * BigUint::from(u128::MAX), 35);
+}
+
+#[test]
+fn test_compatible_below_u64() {
+    let mut cur = Cursor::new(Vec::new());
+    cur.write_varint(70_000u64).unwrap();
+    cur.set_position(0);
+    assert_eq!(BigUint::from(70_000u32), cur.read_varint_big().unwrap());
+}
+
+#[test]
+fn test_compatible_u128() {
+    let values = [
+        240u128,
+        241,
+        2031,
+        u128::from(u64::MAX) + 1,
+        1 << 100,
+        u128::MAX,
+    ];
+    let mut cur = Cursor::new(Vec::new());
+    for v in values {
+        cur.write_varint(v).unwrap();
+    }
+    cur.write_varint_min_width(5u128, 17).unwrap();
+    cur.write_varint(7u8).unwrap();
+    cur.set_position(0);
+    for v in values {
+        assert_eq!(BigUint::from(v), cur.read_varint_big().unwrap());
+    }
+    assert_eq!(BigUint::from(5u8), cur.read_varint_big().unwrap());
+    assert_eq!(BigUint::from(7u8), cur.read_varint_big().unwrap());
+    assert_eq!(cur.get_ref().len() as u64, cur.position());
+}
+
+#[test]
+fn test_truncated() {
+    let mut cur = Cursor::new(vec![255, 10, 1, 2]);
+    assert_eq!(
+        ErrorKind::UnexpectedEof,
+        cur.read_varint_big().unwrap_err().kind()
+    );
+
+    let mut cur = Cursor::new(vec![241, 0, 20, 1, 2]);
+    assert_eq!(
+        ErrorKind::UnexpectedEof,
+        cur.read_varint_big().unwrap_err().kind()
+    );
+}
+
+#[test]
+fn test_marker_ambiguity() {
+    let mut cur = Cursor::new(Vec::new());
+    cur.write_varint_big(&(BigUint::from(u128::MAX) + 1u8))
+        .unwrap();
+    let encoded = cur.into_inner();
+
+    // older readers silently misread the marker as a padded 240
+    let mut r = &encoded[..];
+    assert_eq!(240u128, r.read_varint().unwrap());
+    assert_eq!(17u64, r.read_varint().unwrap());
+    assert_eq!(
+        ErrorKind::InvalidData,
+        ReadVarint::<u128>::read_varint_canonical(&mut &encoded[..])
+            .unwrap_err()
+            .kind()
+    );
+
+    // and a padded 240 followed by other values is out of scope for bigint readers
+    let mut r = &[241u8, 0, 1, 42][..];
+    assert_eq!(BigUint::from(42u8), r.read_varint_big().unwrap());
+}
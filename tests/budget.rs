@@ -0,0 +1,28 @@
+use std::io::{ErrorKind, Read, Result};
+use varuint::{BudgetReader, ReadVarint};
+
+fn read_nested<R: Read>(r: &mut BudgetReader<R>) -> Result<Vec<u8>> {
+    let len: u64 = r.read_varint()?;
+    r.read_bytes(len as usize)
+}
+
+#[test]
+fn test_budget() {
+    let data: &[u8] = &[3, 1, 2, 3, 248, 0, 10];
+    let mut r = BudgetReader::new(data, 6);
+    assert_eq!(vec![1, 2, 3], read_nested(&mut r).unwrap());
+    assert_eq!(4, r.used());
+    assert_eq!(2, r.remaining());
+    assert_eq!(
+        ErrorKind::InvalidData,
+        ReadVarint::<u32>::read_varint(&mut r).unwrap_err().kind()
+    );
+    assert_eq!(6, r.used());
+
+    let mut r = BudgetReader::new(data, 7);
+    r.read_bytes(4).unwrap();
+    assert_eq!(
+        ErrorKind::InvalidData,
+        read_nested(&mut r).unwrap_err().kind()
+    );
+}
@@ -0,0 +1,84 @@
+use varuint::{decode_many, read_varint_slice, VarintError, WriteVarint};
+
+fn decode_one_by_one(mut buf: &[u8]) -> Vec<u64> {
+    let mut values = Vec::new();
+    while !buf.is_empty() {
+        let (v, len) = read_varint_slice::<u64>(buf).unwrap();
+        values.push(v);
+        buf = &buf[len..];
+    }
+    values
+}
+
+#[test]
+fn test_decode_many_matches_scalar() {
+    let mut buf = Vec::new();
+    let mut expected = Vec::new();
+    for i in 0..5000u64 {
+        // long single-byte runs broken up by larger values at varying offsets
+        let v = match i % 37 {
+            0 => i * 1000,
+            5 => u64::MAX - i,
+            11 => 241,
+            _ => i % 241,
+        };
+        buf.write_varint(v).unwrap();
+        expected.push(v);
+    }
+    assert_eq!(expected, decode_one_by_one(&buf));
+
+    let mut out = vec![0u64; expected.len() + 3];
+    assert_eq!(Ok((expected.len(), buf.len())), decode_many(&buf, &mut out));
+    assert_eq!(expected[..], out[..expected.len()]);
+
+    // output full before the input ends
+    let mut out = vec![0u64; 100];
+    let (count, consumed) = decode_many(&buf, &mut out).unwrap();
+    assert_eq!(100, count);
+    assert_eq!(expected[..100], out[..]);
+    let mut rest = vec![0u64; expected.len()];
+    let (count, _) = decode_many(&buf[consumed..], &mut rest).unwrap();
+    assert_eq!(expected[100..], rest[..count]);
+}
+
+#[test]
+fn test_decode_many_block_boundaries() {
+    // every byte value at every position of a 16-byte block
+    for pos in 0..16 {
+        for b in 0..=255u8 {
+            let mut buf = vec![7u8; 32];
+            buf[pos] = b;
+            let mut out = [0u64; 32];
+            match decode_many(&buf, &mut out) {
+                Ok((count, consumed)) => {
+                    assert_eq!(consumed, buf.len());
+                    assert_eq!(decode_one_by_one(&buf)[..], out[..count]);
+                }
+                Err(e) => {
+                    let expected = if b == 255 {
+                        VarintError::Overflow
+                    } else {
+                        VarintError::UnexpectedEof
+                    };
+                    assert_eq!(expected, e);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_decode_many_errors() {
+    let mut out = [0u64; 4];
+    assert_eq!(
+        Err(VarintError::UnexpectedEof),
+        decode_many(&[1, 2, 250, 0], &mut out)
+    );
+    assert_eq!([1, 2], out[..2]);
+    assert_eq!(
+        Err(VarintError::Overflow),
+        decode_many(&[255; 17], &mut out)
+    );
+    assert_eq!(Ok((0, 0)), decode_many(&[], &mut out));
+    assert_eq!(Ok((0, 0)), decode_many(&[1, 2], &mut []));
+}
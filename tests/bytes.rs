@@ -0,0 +1,44 @@
+#![cfg(feature = "bytes")]
+
+use bytes::{Buf, BytesMut};
+use varuint::{VarintBuf, VarintBufMut, VarintError, WriteVarint};
+
+#[test]
+fn test_put_get_varint() {
+    let values = [0u32, 240, 241, 2032, 67568, u32::MAX];
+    let mut buf = BytesMut::new();
+    let mut expected = Vec::new();
+    for v in values {
+        assert_eq!(expected.write_varint(v).unwrap(), buf.put_varint(v));
+    }
+    assert_eq!(expected[..], buf[..]);
+
+    let mut b = buf.freeze();
+    for v in values {
+        assert_eq!(v, b.get_varint::<u32>().unwrap());
+    }
+    assert_eq!(Err(VarintError::UnexpectedEof), b.get_varint::<u32>());
+}
+
+#[test]
+fn test_get_varint_chained() {
+    let mut encoded = Vec::new();
+    encoded.write_varint(-70_000i64).unwrap();
+    encoded.write_varint(u128::MAX).unwrap();
+    // split inside both values
+    let (a, b) = encoded.split_at(2);
+    let (b, c) = b.split_at(5);
+    let mut chain = a.chain(b).chain(c);
+    assert_eq!(-70_000i64, chain.get_varint().unwrap());
+    assert_eq!(u128::MAX, chain.get_varint().unwrap());
+    assert!(!chain.has_remaining());
+
+    let (a, b) = encoded.split_at(1);
+    let mut chain = a.chain(&b[..1]);
+    assert_eq!(Err(VarintError::UnexpectedEof), chain.get_varint::<i64>());
+    assert_eq!(2, chain.remaining());
+
+    let mut short: &[u8] = &[255, 0];
+    assert_eq!(Err(VarintError::Overflow), short.get_varint::<u64>());
+    assert_eq!(2, short.remaining());
+}
@@ -0,0 +1,20 @@
+use std::io::Write;
+use varuint::{CountingWriter, Serializable, Varint, WriteVarint};
+
+#[test]
+fn test_counting_writer() {
+    let values = [0i128, -1, 5000, i128::MIN, i128::from(u64::MAX)];
+    let mut counter = CountingWriter::new();
+    let mut buf = Vec::new();
+    for v in values {
+        assert_eq!(
+            Varint(v).serialize(&mut counter).unwrap(),
+            Varint(v).serialize(&mut buf).unwrap()
+        );
+    }
+    counter.write_varint(u32::MAX).unwrap();
+    buf.write_varint(u32::MAX).unwrap();
+    counter.write_all(b"tail").unwrap();
+    buf.write_all(b"tail").unwrap();
+    assert_eq!(buf.len(), counter.count());
+}
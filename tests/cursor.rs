@@ -0,0 +1,51 @@
+use varuint::{SliceCursor, SliceCursorMut, VarintError};
+
+#[test]
+fn test_roundtrip() {
+    let mut buf = [0u8; 8];
+    let mut w = SliceCursorMut::new(&mut buf);
+    assert_eq!(Ok(2), w.write_varint(300u32));
+    assert_eq!(Ok(1), w.write_varint(-1i64));
+    assert_eq!(3, w.position());
+    assert_eq!(5, w.remaining());
+    assert_eq!(Err(VarintError::BufferTooSmall), w.write_varint(u64::MAX));
+    assert_eq!(3, w.position());
+
+    let mut r = SliceCursor::new(&buf[..3]);
+    assert_eq!(Ok(300u32), r.read_varint());
+    assert_eq!(2, r.position());
+    assert_eq!(Ok(-1i64), r.read_varint());
+    assert_eq!(0, r.remaining());
+    assert_eq!(Err(VarintError::UnexpectedEof), r.read_varint::<u8>());
+
+    let mut r = SliceCursor::new(&[249, 0, 0]);
+    assert_eq!(Err(VarintError::UnexpectedEof), r.read_varint::<u32>());
+    assert_eq!(0, r.position());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_io() {
+    use std::io::ErrorKind;
+    use varuint::{ReadVarint, WriteVarint};
+
+    let mut buf = [0u8; 8];
+    let mut w = SliceCursorMut::new(&mut buf);
+    WriteVarint::write_varint(&mut w, 300u32).unwrap();
+    WriteVarint::write_varint(&mut w, -1i64).unwrap();
+    assert_eq!(3, w.position());
+    assert_eq!(
+        ErrorKind::WriteZero,
+        WriteVarint::write_varint(&mut w, u64::MAX)
+            .unwrap_err()
+            .kind()
+    );
+
+    let mut r = SliceCursor::new(&buf[..3]);
+    assert_eq!(300u32, ReadVarint::read_varint(&mut r).unwrap());
+    assert_eq!(-1i64, ReadVarint::read_varint(&mut r).unwrap());
+    assert_eq!(
+        ErrorKind::UnexpectedEof,
+        ReadVarint::<u8>::read_varint(&mut r).unwrap_err().kind()
+    );
+}
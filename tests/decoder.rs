@@ -0,0 +1,88 @@
+use std::io::{Cursor, Read, Result};
+use varuint::{VarintDecoder, VarintError, WriteVarint};
+
+struct ShortReads<'a>(&'a [u8], usize);
+
+impl Read for ShortReads<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.1 = self.1 % 3 + 1;
+        let n = self.1.min(buf.len()).min(self.0.len());
+        buf[..n].copy_from_slice(&self.0[..n]);
+        self.0 = &self.0[n..];
+        Ok(n)
+    }
+}
+
+#[test]
+fn test_decoder_reuse() {
+    let values: Vec<u128> = (0..2000u128).map(|i| i * i * i * i * i * i * i).collect();
+    let mut cur = Cursor::new(Vec::new());
+    for v in &values {
+        cur.write_varint(*v).unwrap();
+    }
+    let encoded = cur.into_inner();
+
+    let mut decoder = VarintDecoder::<u128>::new();
+    let mut out = Vec::new();
+    assert_eq!(
+        values.len(),
+        decoder
+            .decode_all(&mut ShortReads(&encoded, 0), &mut out)
+            .unwrap()
+    );
+    assert_eq!(values, out);
+    assert!(!decoder.is_pending());
+
+    let mut out = Vec::new();
+    let split = encoded.len() - 3;
+    decoder
+        .decode_all(&mut &encoded[..split], &mut out)
+        .unwrap();
+    assert!(decoder.is_pending());
+    decoder
+        .decode_all(&mut &encoded[split..], &mut out)
+        .unwrap();
+    assert_eq!(values, out);
+}
+
+#[test]
+fn test_decoder_reset() {
+    let mut decoder = VarintDecoder::<u16>::new();
+    assert!(decoder.push(248).is_none());
+    decoder.reset();
+    assert_eq!(5, decoder.push(5).unwrap().unwrap());
+    // a form wider than `u16` fails on its leading byte
+    let err = decoder.push(250).unwrap().unwrap_err();
+    assert_eq!(
+        Some(&VarintError::Overflow),
+        err.get_ref().and_then(|e| e.downcast_ref())
+    );
+    assert!(!decoder.is_pending());
+    assert_eq!(7, decoder.push(7).unwrap().unwrap());
+}
+
+#[test]
+fn test_decoder_resume_after_error() {
+    let mut decoder = VarintDecoder::<u8>::new();
+    let mut out = Vec::new();
+    assert!(decoder
+        .decode_all(&mut &[1u8, 249, 2, 3][..], &mut out)
+        .is_err());
+    assert_eq!(vec![1], out);
+    assert_eq!(3, decoder.decode_all(&mut &[4u8][..], &mut out).unwrap());
+    assert_eq!(vec![1, 2, 3, 4], out);
+}
+
+#[test]
+fn test_decoder_remaining() {
+    let mut decoder = VarintDecoder::<u64>::new();
+    assert_eq!(0, decoder.remaining());
+    assert!(decoder.push(251).is_none());
+    assert_eq!(5, decoder.remaining());
+    for b in [1, 2, 3, 4] {
+        assert!(decoder.push(b).is_none());
+    }
+    assert_eq!(1, decoder.remaining());
+    assert_eq!(0x0504030201, decoder.push(5).unwrap().unwrap());
+    assert_eq!(0, decoder.remaining());
+}
@@ -0,0 +1,85 @@
+use std::io::{Cursor, ErrorKind};
+use varuint::{
+    read_varint_deltas, read_zigzag_deltas, write_varint_deltas, write_zigzag_deltas, WriteVarint,
+};
+
+#[test]
+fn test_zigzag_deltas() {
+    let values = [
+        1_700_000_000i64,
+        1_700_000_003,
+        1_699_999_990,
+        1_700_000_100,
+        -5,
+    ];
+    let mut cur = Cursor::new(Vec::new());
+    let size = write_zigzag_deltas(&mut cur, &values).unwrap();
+    assert_eq!(size, cur.position() as usize);
+    assert_eq!(5 + 1 + 1 + 1 + 5, size);
+    cur.set_position(0);
+    assert_eq!(
+        values.to_vec(),
+        read_zigzag_deltas(&mut cur, values.len()).unwrap()
+    );
+
+    assert!(read_zigzag_deltas(&mut &[][..], 0).unwrap().is_empty());
+    assert_eq!(
+        ErrorKind::InvalidInput,
+        write_zigzag_deltas(&mut Vec::new(), &[i64::MIN, i64::MAX])
+            .unwrap_err()
+            .kind()
+    );
+
+    let mut cur = Cursor::new(Vec::new());
+    cur.write_varint(i64::MAX).unwrap();
+    cur.write_varint(1i64).unwrap();
+    cur.set_position(0);
+    assert_eq!(
+        ErrorKind::InvalidData,
+        read_zigzag_deltas(&mut cur, 2).unwrap_err().kind()
+    );
+    assert_eq!(
+        ErrorKind::UnexpectedEof,
+        read_zigzag_deltas(&mut &[5u8][..], 2).unwrap_err().kind()
+    );
+}
+
+#[test]
+fn test_varint_deltas() {
+    let values = [10u64, 12, 11, 11, 250, 0, 3];
+    let mut cur = Cursor::new(Vec::new());
+    let size = write_varint_deltas(&mut cur, &values).unwrap();
+    assert_eq!(size, cur.position() as usize);
+    assert_eq!(1 + 1 + 1 + 1 + 2 + 2 + 1, size);
+    cur.set_position(0);
+    assert_eq!(
+        values.to_vec(),
+        read_varint_deltas(&mut cur, values.len()).unwrap()
+    );
+
+    assert_eq!(
+        ErrorKind::InvalidInput,
+        write_varint_deltas(&mut Vec::new(), &[0, u64::MAX])
+            .unwrap_err()
+            .kind()
+    );
+
+    let mut cur = Cursor::new(Vec::new());
+    cur.write_varint(u64::MAX).unwrap();
+    cur.write_varint(1i64).unwrap();
+    cur.write_varint(0u64).unwrap();
+    cur.write_varint(-1i64).unwrap();
+    let buf = cur.into_inner();
+    for input in [&buf[..10], &buf[10..]] {
+        assert_eq!(
+            ErrorKind::InvalidData,
+            read_varint_deltas(&mut &input[..], 2).unwrap_err().kind()
+        );
+    }
+
+    assert!(read_varint_deltas(&mut &[][..], 0).unwrap().is_empty());
+    assert_eq!(
+        ErrorKind::UnexpectedEof,
+        read_varint_deltas(&mut &[5u8][..], 2).unwrap_err().kind()
+    );
+}
@@ -0,0 +1,36 @@
+use varuint::{detect_scheme, write_uleb128, write_varint_ordered, Scheme, WriteVarint};
+
+#[test]
+fn test_detect_scheme() {
+    let values = [0u64, 127, 300, 70_000, 1 << 33, u64::MAX - 5];
+    let mut varint = Vec::new();
+    let mut ordered = Vec::new();
+    let mut leb128 = Vec::new();
+    for v in values {
+        varint.write_varint(v).unwrap();
+        let mut buf = [0u8; 17];
+        let len = write_varint_ordered(&mut buf, v).unwrap();
+        ordered.extend_from_slice(&buf[..len]);
+        write_uleb128(&mut leb128, v).unwrap();
+    }
+    assert_eq!(Some(Scheme::Varint), detect_scheme(&varint));
+    assert_eq!(Some(Scheme::Ordered), detect_scheme(&ordered));
+    assert_eq!(Some(Scheme::Leb128), detect_scheme(&leb128));
+
+    // a guess is never a wrong scheme
+    for v in 0..100_000u64 {
+        let mut buf = Vec::new();
+        buf.write_varint(v * 4099).unwrap();
+        assert_ne!(Some(Scheme::Ordered), detect_scheme(&buf));
+        assert_ne!(Some(Scheme::Leb128), detect_scheme(&buf));
+    }
+
+    assert_eq!(None, detect_scheme(&[]));
+    assert_eq!(None, detect_scheme(&[5, 100]));
+    // truncated in every scheme
+    assert_eq!(None, detect_scheme(&[255]));
+    // minimal in both byte orders
+    assert_eq!(None, detect_scheme(&[249, 0x10, 0x00, 0x02, 241, 0x80]));
+    // LEB128 values end within 10 bytes
+    assert_eq!(None, detect_scheme(&[0xff; 11]));
+}
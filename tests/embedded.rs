@@ -0,0 +1,71 @@
+#![cfg(feature = "embedded-io")]
+
+use embedded_io::{ErrorKind, ErrorType, Read, SliceWriteError};
+use varuint::{
+    read_varint_embedded, write_varint_embedded, EmbeddedReadError, VarintError, WriteVarint,
+};
+
+struct Failing;
+
+impl ErrorType for Failing {
+    type Error = ErrorKind;
+}
+
+impl Read for Failing {
+    fn read(&mut self, _buf: &mut [u8]) -> Result<usize, ErrorKind> {
+        Err(ErrorKind::TimedOut)
+    }
+}
+
+#[test]
+fn test_embedded_roundtrip() {
+    let values = [0i64, -1, 120, -2000, 70_000, i64::MIN, i64::MAX];
+    let mut buf = [0u8; 64];
+    let mut w = &mut buf[..];
+    let mut total = 0;
+    for v in values {
+        total += write_varint_embedded(&mut w, v).unwrap();
+    }
+
+    let mut expected = Vec::new();
+    for v in values {
+        expected.write_varint(v).unwrap();
+    }
+    assert_eq!(expected[..], buf[..total]);
+
+    let mut r = &buf[..total];
+    for v in values {
+        assert_eq!(v, read_varint_embedded::<i64, _>(&mut r).unwrap());
+    }
+    assert_eq!(
+        Err(EmbeddedReadError::Varint(VarintError::UnexpectedEof)),
+        read_varint_embedded::<i64, _>(&mut r)
+    );
+}
+
+#[test]
+fn test_embedded_errors() {
+    let mut r: &[u8] = &[250, 1, 2];
+    assert_eq!(
+        Err(EmbeddedReadError::Varint(VarintError::UnexpectedEof)),
+        read_varint_embedded::<u32, _>(&mut r)
+    );
+
+    let mut r: &[u8] = &[251, 1, 2, 3, 4, 5];
+    assert_eq!(
+        Err(EmbeddedReadError::Varint(VarintError::Overflow)),
+        read_varint_embedded::<u32, _>(&mut r)
+    );
+    assert_eq!(5, r.len());
+
+    assert_eq!(
+        Err(EmbeddedReadError::Io(ErrorKind::TimedOut)),
+        read_varint_embedded::<u8, _>(&mut Failing)
+    );
+
+    let mut buf = [0u8; 3];
+    assert_eq!(
+        Err(SliceWriteError::Full),
+        write_varint_embedded(&mut &mut buf[..], u32::MAX)
+    );
+}
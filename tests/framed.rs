@@ -0,0 +1,45 @@
+use std::io::ErrorKind;
+use varuint::{FramedReader, FramedWriter};
+
+#[test]
+fn test_framed_roundtrip() {
+    let messages: Vec<Vec<u8>> = vec![vec![], vec![1, 2, 3], vec![7; 240], vec![9; 5000]];
+    let mut w = FramedWriter::new(Vec::new());
+    let mut total = 0;
+    for m in &messages {
+        total += w.write_message(m).unwrap();
+    }
+    assert_eq!(total, w.bytes_written());
+    let buf = w.into_inner();
+    assert_eq!(total, buf.len());
+    assert_eq!(1 + 1 + 3 + 1 + 240 + 3 + 5000, total);
+
+    let mut r = FramedReader::new(&buf[..], 5000);
+    for m in &messages {
+        assert_eq!(*m, r.read_message().unwrap());
+    }
+    assert_eq!(
+        ErrorKind::UnexpectedEof,
+        r.read_message().unwrap_err().kind()
+    );
+}
+
+#[test]
+fn test_framed_max_frame_size() {
+    let mut w = FramedWriter::new(Vec::new());
+    w.write_message(&[0; 10]).unwrap();
+    let buf = w.into_inner();
+
+    let mut r = FramedReader::new(&buf[..], 9);
+    assert_eq!(ErrorKind::InvalidData, r.read_message().unwrap_err().kind());
+
+    // a forged length is rejected before reading the payload
+    let mut r = FramedReader::new(&[255u8; 17][..], 1 << 20);
+    assert_eq!(ErrorKind::InvalidData, r.read_message().unwrap_err().kind());
+
+    let mut r = FramedReader::new(&buf[..5], 10);
+    assert_eq!(
+        ErrorKind::UnexpectedEof,
+        r.read_message().unwrap_err().kind()
+    );
+}
@@ -0,0 +1,30 @@
+use varuint::{VarintError, VarintIter, WriteVarint};
+
+#[test]
+fn test_varint_iter() {
+    let values = [0i64, -1, 300, -70_000, i64::MIN, i64::MAX];
+    let mut buf = Vec::new();
+    for v in values {
+        buf.write_varint(v).unwrap();
+    }
+
+    let mut iter = VarintIter::<i64>::new(&buf);
+    let decoded: Result<Vec<_>, _> = iter.by_ref().collect();
+    assert_eq!(values[..], decoded.unwrap()[..]);
+    assert!(iter.remainder().is_empty());
+    assert_eq!(None, iter.next());
+
+    let truncated = &buf[..buf.len() - 1];
+    let mut iter = VarintIter::<i64>::new(truncated);
+    assert_eq!(5, iter.by_ref().take_while(Result::is_ok).count());
+    assert_eq!(None, iter.next());
+    assert_eq!(9 - 1, iter.remainder().len());
+
+    let mut iter = VarintIter::<u8>::new(&[1, 255, 0]);
+    assert_eq!(Some(Ok(1)), iter.next());
+    assert_eq!(Some(Err(VarintError::Overflow)), iter.next());
+    assert_eq!(None, iter.next());
+    assert_eq!([255, 0], iter.remainder());
+
+    assert_eq!(None, VarintIter::<u32>::new(&[]).next());
+}
@@ -0,0 +1,72 @@
+use std::io::ErrorKind;
+use varuint::{read_sleb128, read_uleb128, write_sleb128, write_uleb128};
+
+#[test]
+fn test_uleb128() {
+    for (v, bytes) in [
+        (0u64, &[0x00][..]),
+        (2, &[0x02]),
+        (127, &[0x7f]),
+        (128, &[0x80, 0x01]),
+        (624_485, &[0xe5, 0x8e, 0x26]),
+        (
+            u64::MAX,
+            &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01],
+        ),
+    ] {
+        let mut buf = Vec::new();
+        assert_eq!(bytes.len(), write_uleb128(&mut buf, v).unwrap());
+        assert_eq!(bytes, &buf[..]);
+        assert_eq!(v, read_uleb128(&mut &buf[..]).unwrap());
+    }
+
+    // padded encodings are accepted
+    assert_eq!(1, read_uleb128(&mut &[0x81, 0x80, 0x00][..]).unwrap());
+
+    let overflow = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02];
+    assert_eq!(
+        ErrorKind::InvalidData,
+        read_uleb128(&mut &overflow[..]).unwrap_err().kind()
+    );
+    assert_eq!(
+        ErrorKind::UnexpectedEof,
+        read_uleb128(&mut &[0x80, 0x80][..]).unwrap_err().kind()
+    );
+}
+
+#[test]
+fn test_sleb128() {
+    for (v, bytes) in [
+        (0i64, &[0x00][..]),
+        (2, &[0x02]),
+        (-2, &[0x7e]),
+        (63, &[0x3f]),
+        (64, &[0xc0, 0x00]),
+        (-64, &[0x40]),
+        (-65, &[0xbf, 0x7f]),
+        (-123_456, &[0xc0, 0xbb, 0x78]),
+        (
+            i64::MIN,
+            &[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7f],
+        ),
+        (
+            i64::MAX,
+            &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00],
+        ),
+    ] {
+        let mut buf = Vec::new();
+        assert_eq!(bytes.len(), write_sleb128(&mut buf, v).unwrap());
+        assert_eq!(bytes, &buf[..]);
+        assert_eq!(v, read_sleb128(&mut &buf[..]).unwrap());
+    }
+
+    let overflow = [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x01];
+    assert_eq!(
+        ErrorKind::InvalidData,
+        read_sleb128(&mut &overflow[..]).unwrap_err().kind()
+    );
+    assert_eq!(
+        ErrorKind::UnexpectedEof,
+        read_sleb128(&mut &[0xc0][..]).unwrap_err().kind()
+    );
+}
@@ -0,0 +1,32 @@
+use varuint::{varint_newtype, Deserializable, Serializable, Varint};
+
+varint_newtype!(
+    /// Test identifier
+    UserId,
+    u64
+);
+varint_newtype!(pub(crate) Offset, i32);
+
+fn test_same_encoding<N, T>(n: N, v: Varint<T>)
+where
+    N: Serializable + Deserializable + PartialEq + std::fmt::Debug,
+    T: varuint::VarintBaseType,
+    Varint<T>: Serializable,
+{
+    let mut buf = Vec::new();
+    let mut expected = Vec::new();
+    assert_eq!(n.size_hint(), v.size_hint());
+    n.serialize(&mut buf).unwrap();
+    v.serialize(&mut expected).unwrap();
+    assert_eq!(expected, buf);
+    assert_eq!(n, N::deserialize(&mut &buf[..]).unwrap());
+}
+
+#[test]
+fn test_newtype() {
+    test_same_encoding(UserId::from(u64::MAX), Varint(u64::MAX));
+    test_same_encoding(UserId::from(Varint(2031)), Varint(2031u64));
+    test_same_encoding(Offset::from(-70_000), Varint(-70_000i32));
+    assert_eq!(5u64, u64::from(UserId::from(5)));
+    assert_eq!("-3", Offset::from(-3).to_string());
+}
@@ -0,0 +1,23 @@
+#![cfg(feature = "decode-observer")]
+
+use std::sync::Mutex;
+use varuint::{set_decode_observer, ReadVarint};
+
+static SEEN: Mutex<Vec<(u128, usize)>> = Mutex::new(Vec::new());
+
+fn record(v: u128, len: usize) {
+    SEEN.lock().unwrap().push((v, len));
+}
+
+#[test]
+fn test_observer() {
+    set_decode_observer(Some(record));
+    let mut buf: &[u8] = &[5, 249, 0xf0, 0x07, 0x01, 3, 248];
+    assert_eq!(5u8, buf.read_varint().unwrap());
+    assert_eq!(67568u64, buf.read_varint().unwrap());
+    assert_eq!(-2i16, buf.read_varint().unwrap());
+    assert!(ReadVarint::<u32>::read_varint(&mut buf).is_err());
+    set_decode_observer(None);
+    assert_eq!(0u8, (&[0u8][..]).read_varint().unwrap());
+    assert_eq!(vec![(5, 1), (67568, 4), (3, 1)], *SEEN.lock().unwrap());
+}
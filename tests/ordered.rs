@@ -0,0 +1,111 @@
+use std::cmp::Ordering;
+use varuint::{read_varint_ordered, write_varint_ordered, OrderedVarint, VarintError};
+
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    // spread values over all length classes
+    fn value(&mut self) -> u128 {
+        let v = u128::from(self.next()) << 64 | u128::from(self.next());
+        v >> (self.next() % 128)
+    }
+}
+
+fn encode<T: OrderedVarint + PartialEq + std::fmt::Debug>(v: T) -> Vec<u8> {
+    let mut buf = [0u8; 17];
+    let len = write_varint_ordered(&mut buf, v).unwrap();
+    assert_eq!(len, v.varint_size());
+    assert_eq!((v, len), read_varint_ordered::<T>(&buf[..len]).unwrap());
+    buf[..len].to_vec()
+}
+
+fn check_pair<T: OrderedVarint + Ord + std::fmt::Debug>(a: T, b: T) {
+    assert_eq!(a.cmp(&b), encode(a).cmp(&encode(b)), "{:?} {:?}", a, b);
+}
+
+#[test]
+fn test_ordered_boundaries() {
+    let edges = [
+        0u64,
+        240,
+        241,
+        2031,
+        2032,
+        67823,
+        67824,
+        (1 << 24) - 1,
+        1 << 24,
+        u64::from(u32::MAX),
+        1 << 32,
+        (1 << 56) - 1,
+        1 << 56,
+        u64::MAX,
+    ];
+    for &a in &edges {
+        for &b in &edges {
+            check_pair(a, b);
+            check_pair(a.saturating_sub(1), b);
+        }
+    }
+    check_pair(u128::from(u64::MAX), u128::from(u64::MAX) + 1);
+    check_pair(u128::MAX - 1, u128::MAX);
+}
+
+#[test]
+fn test_ordered_random_pairs() {
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..20_000 {
+        let (a, b) = (rng.value(), rng.value());
+        check_pair(a, b);
+        check_pair(a as u64, b as u64);
+        check_pair(a as u32, b as u32);
+        check_pair(a as u16, b as u16);
+        check_pair(a as u8, b as u8);
+    }
+}
+
+#[test]
+fn test_ordered_concatenated_keys() {
+    let mut rng = XorShift(42);
+    let mut keys: Vec<((u64, u32), Vec<u8>)> = (0..2000)
+        .map(|_| {
+            let k = (rng.value() as u64 % 5000, rng.value() as u32);
+            let mut bytes = encode(k.0);
+            bytes.extend(encode(k.1));
+            (k, bytes)
+        })
+        .collect();
+    keys.sort_by(|a, b| a.1.cmp(&b.1));
+    assert!(keys
+        .windows(2)
+        .all(|w| w[0].0.cmp(&w[1].0) != Ordering::Greater));
+}
+
+#[test]
+fn test_ordered_errors() {
+    let mut buf = [0u8; 17];
+    assert_eq!(
+        Err(VarintError::BufferTooSmall),
+        write_varint_ordered(&mut buf[..3], 1u32 << 20)
+    );
+    assert_eq!([0u8; 17], buf);
+    assert_eq!(
+        Err(VarintError::UnexpectedEof),
+        read_varint_ordered::<u32>(&[250, 0, 0])
+    );
+    assert_eq!(
+        Err(VarintError::Overflow),
+        read_varint_ordered::<u32>(&[251, 0, 0, 0, 0, 0])
+    );
+    assert_eq!(
+        Err(VarintError::UnexpectedEof),
+        read_varint_ordered::<u8>(&[])
+    );
+}
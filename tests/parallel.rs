@@ -0,0 +1,35 @@
+#![cfg(feature = "rayon")]
+
+use std::io::{Cursor, ErrorKind};
+use varuint::{par_decode_offsets, ReadVarint, WriteVarint};
+
+#[test]
+fn test_matches_scalar() {
+    let mut cur = Cursor::new(Vec::new());
+    for i in 0..100_000u64 {
+        cur.write_varint(i.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> (i % 64))
+            .unwrap();
+    }
+    let buf = cur.into_inner();
+
+    let mut scalar = Vec::new();
+    let mut r = &buf[..];
+    while !r.is_empty() {
+        let v: u64 = r.read_varint().unwrap();
+        scalar.push(v as usize);
+    }
+    assert_eq!(scalar, par_decode_offsets(&buf).unwrap());
+}
+
+#[test]
+fn test_truncated() {
+    assert!(par_decode_offsets(&[]).unwrap().is_empty());
+    assert_eq!(
+        ErrorKind::UnexpectedEof,
+        par_decode_offsets(&[1, 250, 0]).unwrap_err().kind()
+    );
+    assert_eq!(
+        ErrorKind::InvalidData,
+        par_decode_offsets(&[255; 17]).unwrap_err().kind()
+    );
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "std")]
+
+use proptest::prelude::*;
+use std::fmt::Debug;
+use std::io::Cursor;
+use varuint::{ReadVarint, VarintSizeHint, WriteVarint};
+
+const THRESHOLDS: [u128; 11] = [
+    240,
+    2031,
+    67567,
+    (1 << 24) - 1,
+    (1 << 32) - 1,
+    (1 << 40) - 1,
+    (1 << 48) - 1,
+    (1 << 56) - 1,
+    u64::MAX as u128,
+    u128::MAX - 2,
+    0,
+];
+
+fn check_roundtrip<T>(v: T) -> Result<(), TestCaseError>
+where
+    T: Copy + PartialEq + Debug + VarintSizeHint,
+    Vec<u8>: WriteVarint<T>,
+    for<'a> &'a [u8]: ReadVarint<T>,
+{
+    let mut buf = Vec::new();
+    let len = buf.write_varint(v).unwrap();
+    prop_assert_eq!(len, buf.len());
+    prop_assert_eq!(len, v.varint_size());
+    let mut r = &buf[..];
+    prop_assert_eq!(v, r.read_varint().unwrap());
+    prop_assert!(r.is_empty());
+    Ok(())
+}
+
+// values at and around each length class boundary, mixed with uniform ones
+fn near_threshold() -> impl Strategy<Value = u128> {
+    prop_oneof![
+        any::<u128>(),
+        any::<u64>().prop_map(u128::from),
+        (prop::sample::select(&THRESHOLDS[..]), 0u128..3, any::<bool>()).prop_map(
+            |(t, d, up)| if up {
+                t.saturating_add(d)
+            } else {
+                t.saturating_sub(d)
+            }
+        ),
+    ]
+}
+
+macro_rules! roundtrip_tests {
+    ($($name:ident: $ty:ty),+) => {
+        proptest! {
+            $(
+                #[test]
+                fn $name(v in near_threshold(), neg in any::<bool>()) {
+                    // truncation keeps the low bits, so boundaries of narrower types are hit too
+                    let v = v as $ty;
+                    check_roundtrip(v)?;
+                    if neg {
+                        check_roundtrip(v.wrapping_neg())?;
+                    }
+                }
+            )+
+        }
+    };
+}
+
+roundtrip_tests!(
+    roundtrip_u8: u8,
+    roundtrip_u16: u16,
+    roundtrip_u32: u32,
+    roundtrip_u64: u64,
+    roundtrip_u128: u128,
+    roundtrip_i8: i8,
+    roundtrip_i16: i16,
+    roundtrip_i32: i32,
+    roundtrip_i64: i64,
+    roundtrip_i128: i128
+);
+
+proptest! {
+    #[test]
+    fn concatenated_in_order(
+        values in prop::collection::vec(near_threshold(), 0..64),
+        signed in prop::collection::vec(any::<i64>(), 0..64),
+    ) {
+        let mut cur = Cursor::new(Vec::new());
+        let mut size = 0;
+        for (&v, &s) in values.iter().zip(&signed) {
+            size += cur.write_varint(v).unwrap();
+            size += cur.write_varint(s).unwrap();
+        }
+        prop_assert_eq!(size, cur.get_ref().len());
+        cur.set_position(0);
+        for (&v, &s) in values.iter().zip(&signed) {
+            prop_assert_eq!(v, ReadVarint::<u128>::read_varint(&mut cur).unwrap());
+            prop_assert_eq!(s, ReadVarint::<i64>::read_varint(&mut cur).unwrap());
+        }
+        prop_assert_eq!(size as u64, cur.position());
+    }
+}
@@ -1,5 +1,17 @@
-use std::io::Cursor;
-use varuint::{ReadVarint, VarintBaseType, WriteVarint};
+use std::io::{Cursor, ErrorKind};
+use std::num::{NonZeroU32, NonZeroU64, NonZeroU8};
+use std::time::Duration;
+use varuint::{
+    cmp_encoded, encode_const_u64, encodings_equal, read_run_u64, read_varint_dynamic,
+    read_varint_iter, read_varint_narrowing_info, read_varint_slice, read_varint_slice_strict,
+    read_varint_to_buf, skip_varint, skip_varint_slice, try_decode_small, try_read_varint_bufread,
+    validate_exact_count, varint_len_from_first_byte, varint_ranges, varint_size, varint_size_i32,
+    varint_size_i64, varint_size_i8, varint_size_u128, varint_size_u16, varint_size_u32,
+    varint_size_u64, varint_to_varuint_128, varint_to_varuint_32, varint_to_varuint_64,
+    varuint_to_varint_128, varuint_to_varint_64, write_run_u64, write_varint_slice, zigzag,
+    Deserializable, NarrowFit, ReadVarint, Serializable, Varint, VarintBaseType, VarintCodec,
+    VarintError, VarintSizeHint, VarintValue, WriteVarint,
+};
 
 fn test_varuint<T: VarintBaseType>(v: T, size: usize)
 where
@@ -72,8 +84,1304 @@ fn test_all() {
     test_varuint(72057594037927935u128, 8);
 
     test_varuint(72057594037927936u128, 9);
-    test_varuint(u128::from(u64::max_value()), 9);
+    test_varuint(u128::from(u64::MAX), 9);
 
-    test_varuint(u128::from(u64::max_value()) + 1, 17);
-    test_varuint(u128::max_value(), 17);
+    test_varuint(u128::from(u64::MAX) + 1, 17);
+    test_varuint(u128::MAX, 17);
+}
+
+#[test]
+fn test_read_until_sentinel() {
+    let mut cur = Cursor::new(Vec::new());
+    for v in [5u64, 300, 70_000, 0] {
+        cur.write_varint(v).unwrap();
+    }
+    cur.set_position(0);
+    assert_eq!(
+        vec![5u64, 300, 70_000],
+        cur.read_until_sentinel(0u64, 3).unwrap()
+    );
+
+    cur.set_position(0);
+    assert_eq!(
+        ErrorKind::InvalidData,
+        cur.read_until_sentinel(0u64, 2).unwrap_err().kind()
+    );
+
+    let mut cur = Cursor::new(vec![0u8]);
+    assert!(cur.read_until_sentinel(0u32, 0).unwrap().is_empty());
+
+    let mut cur = Cursor::new(vec![1u8, 2]);
+    assert_eq!(
+        ErrorKind::UnexpectedEof,
+        cur.read_until_sentinel(0u8, 10).unwrap_err().kind()
+    );
+}
+
+#[test]
+fn test_encode_const_u64() {
+    const ENCODED: ([u8; 9], usize) = encode_const_u64(67568);
+    assert_eq!([249, 0xf0, 0x07, 0x01], ENCODED.0[..ENCODED.1]);
+
+    for v in [0u64, 240, 241, 2031, 2032, 67567, 16_777_216, u64::MAX] {
+        let (buf, size) = encode_const_u64(v);
+        let mut cur = Cursor::new(Vec::new());
+        assert_eq!(size, cur.write_varint(v).unwrap());
+        assert_eq!(cur.get_ref()[..], buf[..size]);
+    }
+}
+
+#[test]
+fn test_try_decode_small() {
+    const DECODED: Option<(u64, usize)> = try_decode_small(&[247, 255]);
+    assert_eq!(Some((2031, 2)), DECODED);
+
+    for v in 0..=2031u64 {
+        let (buf, size) = encode_const_u64(v);
+        assert_eq!(Some((v, size)), try_decode_small(&buf[..size]));
+    }
+    for v in [2032u64, 67568, u64::MAX] {
+        let (buf, _) = encode_const_u64(v);
+        assert_eq!(None, try_decode_small(&buf));
+    }
+    assert_eq!(None, try_decode_small(&[]));
+    assert_eq!(None, try_decode_small(&[241]));
+}
+
+#[test]
+fn test_validate_exact_count() {
+    let mut cur = Cursor::new(Vec::new());
+    for v in [1u128, 2031, 67568, u128::MAX] {
+        cur.write_varint(v).unwrap();
+    }
+    let buf = cur.into_inner();
+    assert!(validate_exact_count(&buf, 4).is_ok());
+    assert!(validate_exact_count(&[], 0).is_ok());
+    assert_eq!(
+        ErrorKind::InvalidData,
+        validate_exact_count(&buf, 3).unwrap_err().kind()
+    );
+    assert_eq!(
+        ErrorKind::InvalidData,
+        validate_exact_count(&buf, 5).unwrap_err().kind()
+    );
+    assert_eq!(
+        ErrorKind::UnexpectedEof,
+        validate_exact_count(&buf[..buf.len() - 1], 4)
+            .unwrap_err()
+            .kind()
+    );
+}
+
+#[test]
+fn test_write_varint_min_width() {
+    let mut cur = Cursor::new(Vec::new());
+    assert_eq!(5, cur.write_varint_min_width(7u64, 5).unwrap());
+    assert_eq!(1, cur.write_varint_min_width(8u64, 1).unwrap());
+    assert_eq!(4, cur.write_varint_min_width(-3i32, 2).unwrap());
+    assert_eq!(17, cur.write_varint_min_width(1u128, 10).unwrap());
+    cur.set_position(0);
+    assert_eq!(7u64, cur.read_varint().unwrap());
+    assert_eq!(8u64, cur.read_varint().unwrap());
+    assert_eq!(-3i32, cur.read_varint().unwrap());
+    assert_eq!(1u128, cur.read_varint().unwrap());
+
+    // value grows in place without shifting the following data
+    cur.set_position(0);
+    assert_eq!(5, cur.write_varint_min_width(4_000_000_000u64, 5).unwrap());
+    cur.set_position(0);
+    assert_eq!(4_000_000_000u64, cur.read_varint().unwrap());
+    assert_eq!(8u64, cur.read_varint().unwrap());
+
+    assert_eq!(
+        ErrorKind::InvalidInput,
+        cur.write_varint_min_width(1u16, 4).unwrap_err().kind()
+    );
+    assert_eq!(
+        ErrorKind::InvalidInput,
+        cur.write_varint_min_width(1u128, 18).unwrap_err().kind()
+    );
+
+    // types encoded through integers pad like them, `bool` has no form wider than a `u8`
+    let mut cur = Cursor::new(Vec::new());
+    assert_eq!(1, cur.write_varint_min_width(true, 1).unwrap());
+    assert_eq!(4, cur.write_varint_min_width('a', 4).unwrap());
+    assert_eq!(5, cur.write_varint_min_width(0.0f32, 5).unwrap());
+    assert_eq!(
+        8,
+        cur.write_varint_min_width(Duration::new(1, 5), 4).unwrap()
+    );
+    assert_eq!(18, cur.position());
+    cur.set_position(0);
+    assert!(ReadVarint::<bool>::read_varint(&mut cur).unwrap());
+    assert_eq!('a', ReadVarint::<char>::read_varint(&mut cur).unwrap());
+    assert_eq!(0.0f32, ReadVarint::<f32>::read_varint(&mut cur).unwrap());
+    assert_eq!(
+        Duration::new(1, 5),
+        ReadVarint::<Duration>::read_varint(&mut cur).unwrap()
+    );
+}
+
+struct Celsius(i16);
+
+// implementors outside the crate need `write_varint` only
+impl WriteVarint<Celsius> for Vec<u8> {
+    fn write_varint(&mut self, v: Celsius) -> std::io::Result<usize> {
+        self.write_varint(v.0)
+    }
+}
+
+#[test]
+fn test_write_varint_external_impl() {
+    let mut buf = Vec::new();
+    assert_eq!(1, buf.write_varint(Celsius(-3)).unwrap());
+    assert_eq!(-3i16, (&buf[..]).read_varint().unwrap());
+    assert_eq!(
+        ErrorKind::InvalidInput,
+        buf.write_varint_min_width(Celsius(-3), 4)
+            .unwrap_err()
+            .kind()
+    );
+}
+
+#[test]
+fn test_encodings_equal() {
+    assert!(encodings_equal(&[5], &[5]).unwrap());
+    assert!(!encodings_equal(&[5], &[6]).unwrap());
+    assert!(encodings_equal(&[5], &[249, 5, 0, 0]).unwrap());
+    assert!(encodings_equal(
+        &[250, 1, 1, 0, 0],
+        &[255, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+    )
+    .unwrap());
+    assert_eq!(
+        ErrorKind::UnexpectedEof,
+        encodings_equal(&[249, 5], &[249, 5]).unwrap_err().kind()
+    );
+    assert_eq!(
+        ErrorKind::InvalidData,
+        encodings_equal(&[5], &[5, 0]).unwrap_err().kind()
+    );
+}
+
+#[test]
+fn test_self_delimited() {
+    let mut cur = Cursor::new(Vec::new());
+    assert_eq!(4, cur.write_self_delimited(2032u32).unwrap());
+    assert_eq!(18, cur.write_self_delimited(i128::MIN).unwrap());
+    assert_eq!(2, cur.write_self_delimited(7u8).unwrap());
+    cur.set_position(0);
+    assert_eq!(2032u32, cur.read_self_delimited().unwrap());
+    assert_eq!(i128::MIN, cur.read_self_delimited().unwrap());
+    assert_eq!(7u8, cur.read_self_delimited().unwrap());
+
+    for buf in [vec![2u8, 5, 0], vec![2, 249, 0], vec![18]] {
+        let mut cur = Cursor::new(buf);
+        let res: std::io::Result<u32> = cur.read_self_delimited();
+        assert_eq!(ErrorKind::InvalidData, res.unwrap_err().kind());
+    }
+}
+
+#[test]
+fn test_write_varint_clamped() {
+    let mut cur = Cursor::new(Vec::new());
+    assert_eq!(1, cur.write_varint_clamped(100u64, 200).unwrap());
+    assert_eq!(2, cur.write_varint_clamped(u64::MAX, 1000).unwrap());
+    cur.set_position(0);
+    assert_eq!(100u64, cur.read_varint().unwrap());
+    assert_eq!(1000u64, cur.read_varint().unwrap());
+}
+
+#[test]
+fn test_run() {
+    let mut cur = Cursor::new(Vec::new());
+    assert_eq!(3, cur.write_run(70u64, 300).unwrap());
+    assert_eq!(2, cur.write_run(-5i32, 0).unwrap());
+    assert_eq!(10, cur.write_run(1u8, u64::MAX).unwrap());
+    cur.set_position(0);
+    assert_eq!(vec![70u64; 300], cur.read_run(300).unwrap());
+    assert!(ReadVarint::<i32>::read_run(&mut cur, 0).unwrap().is_empty());
+    assert_eq!(
+        ErrorKind::InvalidData,
+        ReadVarint::<u8>::read_run(&mut cur, 1 << 20)
+            .unwrap_err()
+            .kind()
+    );
+
+    let mut buf = Vec::new();
+    assert_eq!(3, write_run_u64(&mut buf, 70, 300).unwrap());
+    let mut expected = Vec::new();
+    expected.write_run(70u64, 300).unwrap();
+    assert_eq!(expected, buf);
+    assert_eq!(vec![70u64; 300], read_run_u64(&mut &buf[..], 300).unwrap());
+    assert_eq!(
+        ErrorKind::InvalidData,
+        read_run_u64(&mut &buf[..], 299).unwrap_err().kind()
+    );
+}
+
+#[test]
+fn test_read_array() {
+    let mut cur = Cursor::new(Vec::new());
+    for v in [1i64, -300, 1 << 40] {
+        cur.write_varint(v).unwrap();
+    }
+    cur.set_position(0);
+    assert_eq!([1i64, -300, 1 << 40], cur.read_array().unwrap());
+    cur.set_position(0);
+    let empty: [i64; 0] = cur.read_array().unwrap();
+    assert!(empty.is_empty());
+    assert_eq!(0, cur.position());
+    let res: std::io::Result<[i64; 4]> = cur.read_array();
+    assert_eq!(ErrorKind::UnexpectedEof, res.unwrap_err().kind());
+
+    // element types need no `Default`
+    let mut r = &[1u8, 241, 0][..];
+    let ids: [NonZeroU32; 2] = r.read_array().unwrap();
+    assert_eq!([2, 241], ids.map(NonZeroU32::get));
+    let mut buf = Vec::new();
+    buf.write_varint(u32::MAX).unwrap();
+    let res: std::io::Result<[NonZeroU32; 1]> = (&buf[..]).read_array();
+    assert_eq!(ErrorKind::InvalidData, res.unwrap_err().kind());
+}
+
+#[test]
+fn test_read_varint_narrowing_info() {
+    let cases = [
+        (0u128, NarrowFit::U8),
+        (255, NarrowFit::U8),
+        (256, NarrowFit::U16),
+        (65_535, NarrowFit::U16),
+        (65_536, NarrowFit::U32),
+        (u128::from(u32::MAX), NarrowFit::U32),
+        (u128::from(u32::MAX) + 1, NarrowFit::U64),
+        (u128::from(u64::MAX), NarrowFit::U64),
+        (u128::from(u64::MAX) + 1, NarrowFit::U128),
+        (u128::MAX, NarrowFit::U128),
+    ];
+    let mut cur = Cursor::new(Vec::new());
+    for (v, _) in cases {
+        cur.write_varint(v).unwrap();
+    }
+    cur.set_position(0);
+    for case in cases {
+        assert_eq!(case, read_varint_narrowing_info(&mut cur).unwrap());
+    }
+}
+
+#[test]
+fn test_read_varint_dynamic() {
+    let cases = [
+        (0u128, VarintValue::U8(0)),
+        (255, VarintValue::U8(255)),
+        (256, VarintValue::U16(256)),
+        (65_536, VarintValue::U32(65_536)),
+        (
+            u128::from(u32::MAX) + 1,
+            VarintValue::U64(u64::from(u32::MAX) + 1),
+        ),
+        (u128::MAX, VarintValue::U128(u128::MAX)),
+    ];
+    let mut cur = Cursor::new(Vec::new());
+    for (v, _) in cases {
+        cur.write_varint(v).unwrap();
+    }
+    // the type follows the value, not the encoded length
+    cur.write_varint_min_width(7u64, 9).unwrap();
+    cur.set_position(0);
+    for (v, expected) in cases {
+        let value = read_varint_dynamic(&mut cur).unwrap();
+        assert_eq!(expected, value);
+        assert_eq!(v, u128::from(value));
+        assert_eq!(NarrowFit::of(v), value.fit());
+    }
+    assert_eq!(VarintValue::U8(7), read_varint_dynamic(&mut cur).unwrap());
+}
+
+#[test]
+fn test_recover_next() {
+    let mut cur = Cursor::new(vec![5u8, 250, 255, 248, 0, 1, 241, 3, 249, 1]);
+    assert_eq!(Some(5u16), cur.recover_next().unwrap());
+    assert_eq!(Some(2033u16), cur.recover_next().unwrap());
+    assert_eq!(Some(243u16), cur.recover_next().unwrap());
+    assert_eq!(Some(1u16), cur.recover_next().unwrap());
+    assert_eq!(None, ReadVarint::<u16>::recover_next(&mut cur).unwrap());
+
+    let mut cur = Cursor::new(vec![250u8, 1, 2]);
+    assert_eq!(None, ReadVarint::<u32>::recover_next(&mut cur).unwrap());
+
+    // a complete value out of range for the type is skipped as a whole
+    let mut cur = Cursor::new(vec![5u8, 241, 16, 7, 241, 15]);
+    assert_eq!(Some(5u8), cur.recover_next().unwrap());
+    assert_eq!(Some(7u8), cur.recover_next().unwrap());
+    assert_eq!(Some(255u8), cur.recover_next().unwrap());
+    assert_eq!(None, ReadVarint::<u8>::recover_next(&mut cur).unwrap());
+
+    // values spanning several varints, the last one truncated
+    for tail in [&[7u8][..], &[248, 0, 1], &[248, 0]] {
+        let mut buf = Vec::new();
+        buf.write_varint(Duration::new(5, 7)).unwrap();
+        buf.write_varint(Duration::new(70_000, 1_000)).unwrap();
+        buf.extend_from_slice(tail);
+        let mut cur = Cursor::new(buf);
+        assert_eq!(Some(Duration::new(5, 7)), cur.recover_next().unwrap());
+        assert_eq!(
+            Some(Duration::new(70_000, 1_000)),
+            cur.recover_next().unwrap()
+        );
+        assert_eq!(
+            None,
+            ReadVarint::<Duration>::recover_next(&mut cur).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_varint_ranges() {
+    let buf = [5u8, 241, 0, 250, 1, 2, 3, 4, 7];
+    let ranges: Vec<_> = varint_ranges(&buf).map(Result::unwrap).collect();
+    assert_eq!(vec![0..1, 1..3, 3..8, 8..9], ranges);
+    assert_eq!(0, varint_ranges(&[]).count());
+
+    let mut it = varint_ranges(&buf[..7]);
+    assert_eq!(0..1, it.next().unwrap().unwrap());
+    assert_eq!(1..3, it.next().unwrap().unwrap());
+    assert_eq!(
+        ErrorKind::UnexpectedEof,
+        it.next().unwrap().unwrap_err().kind()
+    );
+    assert!(it.next().is_none());
+}
+
+#[test]
+fn test_u128_boundaries() {
+    let mut values = vec![240u128, 241, 2031, 2032, 67567, 67568];
+    for k in 0..128 {
+        let p = 1u128 << k;
+        values.extend([p - 1, p, p + 1]);
+    }
+    values.push(u128::MAX);
+    for v in values {
+        let size = v.varint_size();
+        let mut cur = Cursor::new(Vec::new());
+        assert_eq!(size, cur.write_varint(v).unwrap());
+        assert_eq!(size, cur.get_ref().len());
+        if let Ok(v64) = u64::try_from(v) {
+            let (buf, len) = encode_const_u64(v64);
+            assert_eq!(cur.get_ref()[..], buf[..len]);
+        }
+        cur.set_position(0);
+        assert_eq!(v, ReadVarint::<u128>::read_varint(&mut cur).unwrap());
+    }
+}
+
+#[test]
+fn test_read_varint_slice() {
+    let mut buf = Vec::new();
+    let values = [0u64, 240, 241, 2032, 67568, 1 << 40, u64::MAX];
+    for v in values {
+        buf.write_varint(v).unwrap();
+    }
+    let mut off = 0;
+    for v in values {
+        let (decoded, n) = read_varint_slice::<u64>(&buf[off..]).unwrap();
+        assert_eq!(v, decoded);
+        off += n;
+    }
+    assert_eq!(buf.len(), off);
+
+    assert_eq!(
+        Err(VarintError::UnexpectedEof),
+        read_varint_slice::<u64>(&buf[buf.len() - 9..buf.len() - 1])
+    );
+    assert_eq!(
+        Err(VarintError::UnexpectedEof),
+        read_varint_slice::<u64>(&[])
+    );
+    assert_eq!(
+        Err(VarintError::Overflow),
+        read_varint_slice::<u64>(&[255; 17])
+    );
+    assert_eq!(Ok((-1i16, 1)), read_varint_slice(&[1]));
+}
+
+#[test]
+fn test_write_varint_slice() {
+    let mut scratch = [0u8; 17];
+    for v in [
+        0u128,
+        240,
+        241,
+        2031,
+        2032,
+        67568,
+        1 << 40,
+        u64::MAX.into(),
+        u128::MAX,
+    ] {
+        let size = write_varint_slice(&mut scratch, v).unwrap();
+        let mut expected = Vec::new();
+        assert_eq!(size, expected.write_varint(v).unwrap());
+        assert_eq!(expected[..], scratch[..size]);
+
+        let mut short = vec![0u8; size - 1];
+        assert_eq!(
+            Err(VarintError::BufferTooSmall),
+            write_varint_slice(&mut short, v)
+        );
+        assert!(short.iter().all(|b| *b == 0));
+    }
+    assert_eq!(Ok(1), write_varint_slice(&mut scratch, -1i8));
+    assert_eq!(1, scratch[0]);
+}
+
+fn check_canonical<T>(v: T, widths: &[usize])
+where
+    T: VarintBaseType + std::fmt::Debug,
+    Vec<u8>: WriteVarint<T>,
+    for<'a> &'a [u8]: ReadVarint<T>,
+{
+    let mut buf = Vec::new();
+    buf.write_varint(v).unwrap();
+    assert_eq!(v, (&buf[..]).read_varint_canonical().unwrap());
+    assert_eq!((v, true), (&buf[..]).read_varint_checked().unwrap());
+    for &width in widths.iter().filter(|w| **w > v.varint_size()) {
+        let mut buf = Vec::new();
+        assert_eq!(width, buf.write_varint_min_width(v, width).unwrap());
+        assert_eq!(v, (&buf[..]).read_varint().unwrap());
+        let err = ReadVarint::<T>::read_varint_canonical(&mut &buf[..]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, err.kind());
+        assert_eq!((v, false), (&buf[..]).read_varint_checked().unwrap());
+    }
+}
+
+#[test]
+fn test_read_varint_canonical() {
+    let widths = [4, 5, 6, 7, 8, 9, 17];
+    for v in [0u128, 240, 241, 2031, 2032, 67567, 67568] {
+        check_canonical(v as u32, &widths[..2]);
+        check_canonical(v as u64, &widths[..6]);
+        check_canonical(v, &widths);
+    }
+    for k in 24..128 {
+        let p = 1u128 << k;
+        check_canonical(p - 1, &widths);
+        check_canonical(p, &widths);
+    }
+    check_canonical(u128::from(u64::MAX), &widths);
+    check_canonical(-1i64, &widths[..6]);
+    check_canonical(i128::MIN, &widths);
+
+    let err = ReadVarint::<u64>::read_varint_canonical(&mut &[241u8, 0][..]).unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, err.kind());
+    let err = ReadVarint::<u64>::read_varint_canonical(&mut &[250u8, 0][..]).unwrap_err();
+    assert_eq!(ErrorKind::UnexpectedEof, err.kind());
+    let err = ReadVarint::<u8>::read_varint_canonical(&mut &[241u8, 16][..]).unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, err.kind());
+
+    // too wide forms are rejected after the leading byte
+    let mut r = &[255u8, 1, 2][..];
+    let err = ReadVarint::<u16>::read_varint_canonical(&mut r).unwrap_err();
+    assert_eq!(
+        Some(&VarintError::Overflow),
+        err.get_ref().and_then(|e| e.downcast_ref())
+    );
+    assert_eq!([1, 2], r);
+    let mut r = &[249u8, 1, 2][..];
+    let err = ReadVarint::<u8>::read_varint_checked(&mut r).unwrap_err();
+    assert_eq!(
+        Some(&VarintError::Overflow),
+        err.get_ref().and_then(|e| e.downcast_ref())
+    );
+    assert_eq!([1, 2], r);
+
+    assert_eq!(
+        (240u64, false),
+        (&[241u8, 0][..]).read_varint_checked().unwrap()
+    );
+    let err = ReadVarint::<u8>::read_varint_checked(&mut &[241u8, 16][..]).unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, err.kind());
+}
+
+fn assert_invalid<T: std::fmt::Debug>(buf: &[u8])
+where
+    for<'a> &'a [u8]: ReadVarint<T>,
+{
+    let err = ReadVarint::<T>::read_varint(&mut &buf[..]).unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, err.kind(), "{:?}", buf);
+}
+
+#[test]
+fn test_read_varint_out_of_range() {
+    let body = [0u8; 16];
+    let with_body = |first: u8| [&[first][..], &body[..]].concat();
+
+    assert_eq!(255u8, (&[241u8, 15][..]).read_varint().unwrap());
+    assert_invalid::<u8>(&[241, 16]);
+    for first in 242..=255 {
+        assert_invalid::<u8>(&with_body(first));
+        assert_invalid::<i8>(&with_body(first));
+    }
+
+    assert_eq!(u16::MAX, (&[248u8, 0xf8, 0x0f][..]).read_varint().unwrap());
+    assert_invalid::<u16>(&[248, 0xf8, 0x10]);
+    assert_invalid::<i16>(&[248, 0xff, 0xff]);
+    for first in 249..=255 {
+        assert_invalid::<u16>(&with_body(first));
+    }
+    for first in 251..=255 {
+        assert_invalid::<u32>(&with_body(first));
+        assert_invalid::<i32>(&with_body(first));
+    }
+    assert_invalid::<u64>(&with_body(255));
+    assert_invalid::<i64>(&with_body(255));
+}
+
+#[test]
+fn test_varint_len_from_first_byte() {
+    for first in 0..=255u8 {
+        let len = varint_len_from_first_byte(first);
+        let mut buf = vec![0u8; len];
+        buf[0] = first;
+        let mut r = &buf[..];
+        ReadVarint::<u128>::read_varint(&mut r).unwrap();
+        assert!(r.is_empty(), "{}", first);
+    }
+}
+
+#[test]
+fn test_const_helpers() {
+    const SIZES: [usize; 3] = [
+        varint_size_u64(2031),
+        varint_size_i32(-1),
+        varint_size_u128(u128::MAX),
+    ];
+    assert_eq!([2, 1, 17], SIZES);
+    const ZIGZAG: u64 = varint_to_varuint_64(-2);
+    assert_eq!(3, ZIGZAG);
+    assert_eq!(-2, varuint_to_varint_64(ZIGZAG));
+    assert_eq!(
+        i128::MIN,
+        varuint_to_varint_128(varint_to_varuint_128(i128::MIN))
+    );
+
+    for v in [0i64, -1, 120, -121, 1 << 40, i64::MIN, i64::MAX] {
+        assert_eq!(v.varint_size(), varint_size_i64(v));
+        assert_eq!((v as i8).varint_size(), varint_size_i8(v as i8));
+        assert_eq!((v as u16).varint_size(), varint_size_u16(v as u16));
+        assert_eq!(v.varint_size(), varint_size(v));
+        assert_eq!(
+            varint_size_u32(varint_to_varuint_32(v as i32)),
+            varint_size_i32(v as i32)
+        );
+    }
+}
+
+#[test]
+fn test_generic_varint_size() {
+    let sizes: Vec<usize> = [0u32, 241, 2032, u32::MAX]
+        .iter()
+        .map(varint_size)
+        .collect();
+    assert_eq!(vec![1, 2, 3, 5], sizes);
+    assert_eq!(2, varint_size(NonZeroU8::new(242).unwrap()));
+    assert_eq!(1, varint_size(true));
+    assert_eq!(4, varint_size('😀'));
+}
+
+#[test]
+fn test_pointer_sized() {
+    for v in [0usize, 240, 241, 67568, usize::MAX] {
+        let mut a = Vec::new();
+        let mut b = Vec::new();
+        assert_eq!(
+            a.write_varint(v).unwrap(),
+            b.write_varint(v as u64).unwrap()
+        );
+        assert_eq!(a, b);
+        assert_eq!(v.varint_size(), a.len());
+        assert_eq!(v, (&a[..]).read_varint().unwrap());
+    }
+    for v in [0isize, -1, 120, -121, isize::MIN, isize::MAX] {
+        let mut a = Vec::new();
+        let mut b = Vec::new();
+        assert_eq!(
+            a.write_varint(v).unwrap(),
+            b.write_varint(v as i64).unwrap()
+        );
+        assert_eq!(a, b);
+        assert_eq!(v.varint_size(), a.len());
+        assert_eq!(v, (&a[..]).read_varint().unwrap());
+    }
+
+    let mut wide = Vec::new();
+    wide.write_varint(u64::from(u32::MAX) + 1).unwrap();
+    let res = ReadVarint::<usize>::read_varint(&mut &wide[..]);
+    if cfg!(target_pointer_width = "64") {
+        assert_eq!(u64::from(u32::MAX) + 1, res.unwrap() as u64);
+    } else {
+        assert_eq!(ErrorKind::InvalidData, res.unwrap_err().kind());
+    }
+}
+
+#[test]
+fn test_non_zero() {
+    let one = NonZeroU32::new(1).unwrap();
+    let mut buf = Vec::new();
+    assert_eq!(1, buf.write_varint(one).unwrap());
+    assert_eq!(vec![0], buf);
+    assert_eq!(one, (&buf[..]).read_varint().unwrap());
+
+    for v in [1u64, 241, 242, 2033, u64::MAX] {
+        let v = NonZeroU64::new(v).unwrap();
+        let mut buf = Vec::new();
+        let size = v.serialize(&mut buf).unwrap();
+        assert_eq!(size, v.size_hint());
+        assert_eq!(size, v.varint_size());
+        assert_eq!(v, NonZeroU64::deserialize(&mut &buf[..]).unwrap());
+    }
+
+    let mut buf = Vec::new();
+    buf.write_varint(u8::MAX).unwrap();
+    let err = ReadVarint::<NonZeroU8>::read_varint(&mut &buf[..]).unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, err.kind());
+}
+
+#[test]
+fn test_try_read_varint() {
+    let mut buf = Vec::new();
+    buf.write_varint(67568u32).unwrap();
+    assert_eq!(Ok(67568u32), (&buf[..]).try_read_varint());
+    assert_eq!(
+        Err(VarintError::UnexpectedEof),
+        ReadVarint::<u32>::try_read_varint(&mut &buf[..2])
+    );
+    assert_eq!(
+        Err(VarintError::UnexpectedEof),
+        ReadVarint::<u32>::try_read_varint(&mut &[][..])
+    );
+
+    // rejected without consuming the body
+    let mut r = &[251u8, 0, 0, 0, 0, 0][..];
+    assert_eq!(
+        Err(VarintError::Overflow),
+        ReadVarint::<u32>::try_read_varint(&mut r)
+    );
+    assert_eq!(5, r.len());
+
+    assert_eq!(
+        Err(VarintError::Overflow),
+        ReadVarint::<u8>::try_read_varint(&mut &[241u8, 16][..])
+    );
+
+    let mut buf = Vec::new();
+    buf.write_varint_min_width(5u64, 4).unwrap();
+    let err = ReadVarint::<u64>::read_varint_canonical(&mut &buf[..]).unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, err.kind());
+    assert_eq!(
+        Some(&VarintError::NonCanonical),
+        err.get_ref().and_then(|e| e.downcast_ref())
+    );
+
+    let io: std::io::Error = VarintError::Overflow.into();
+    assert_eq!(ErrorKind::InvalidData, io.kind());
+    let e: VarintError = std::io::Error::from(ErrorKind::BrokenPipe).into();
+    assert_eq!(VarintError::Io(ErrorKind::BrokenPipe), e);
+}
+
+#[test]
+fn test_read_varint_iter() {
+    let mut buf = Vec::new();
+    for v in [0u64, 240, 241, 2032, 67824, u64::from(u32::MAX), u64::MAX] {
+        buf.write_varint(v).unwrap();
+    }
+    let mut it = buf.iter().copied();
+    for v in [0u64, 240, 241, 2032, 67824, u64::from(u32::MAX), u64::MAX] {
+        assert_eq!(Ok(v), read_varint_iter(&mut it));
+    }
+    assert_eq!(
+        Err(VarintError::UnexpectedEof),
+        read_varint_iter::<u64>(&mut it)
+    );
+
+    let mut it = [250u8, 1, 2, 5].iter().copied();
+    assert_eq!(
+        Err(VarintError::UnexpectedEof),
+        read_varint_iter::<u32>(&mut it)
+    );
+    assert_eq!(None, it.next());
+
+    // the body of a too wide form is left in the iterator
+    let mut it = [251u8, 1, 2].iter().copied();
+    assert_eq!(Err(VarintError::Overflow), read_varint_iter::<u32>(&mut it));
+    assert_eq!(Some(1), it.next());
+}
+
+#[test]
+fn test_zigzag_module() {
+    assert_eq!(0u8, zigzag::encode(0i8));
+    assert_eq!(3u16, zigzag::encode(-2i16));
+    assert_eq!(u64::MAX, zigzag::encode(i64::MIN));
+    assert_eq!(u64::MAX - 1, zigzag::encode(i64::MAX));
+    assert_eq!(1usize, zigzag::encode(-1isize));
+    assert_eq!(isize::MIN, zigzag::decode(usize::MAX));
+    for v in [0i128, 1, -1, i128::MIN, i128::MAX] {
+        assert_eq!(varint_to_varuint_128(v), zigzag::encode(v));
+        assert_eq!(v, zigzag::decode(zigzag::encode(v)));
+    }
+}
+
+#[test]
+fn test_write_references() {
+    let values = [1u64, 300, 70_000, u64::MAX];
+    let mut by_ref = Vec::new();
+    values
+        .iter()
+        .try_for_each(|v| by_ref.write_varint(v).map(drop))
+        .unwrap();
+    let mut by_value = Vec::new();
+    for v in values {
+        by_value.write_varint(v).unwrap();
+    }
+    assert_eq!(by_value, by_ref);
+    assert_eq!(
+        by_ref.len(),
+        values
+            .iter()
+            .map(VarintSizeHint::varint_size)
+            .sum::<usize>()
+    );
+
+    let mut buf = Vec::new();
+    assert_eq!(4, buf.write_varint_min_width(&-1i32, 4).unwrap());
+    assert_eq!(Ok(-1i32), ReadVarint::<i32>::try_read_varint(&mut &buf[..]));
+}
+
+#[test]
+fn test_char() {
+    for (c, size) in [
+        ('\0', 1),
+        ('a', 1),
+        ('\u{f0}', 1),
+        ('\u{7ef}', 2),
+        ('€', 3),
+        ('\u{ffff}', 3),
+        ('😀', 4),
+        (char::MAX, 4),
+    ] {
+        assert_eq!(size, c.varint_size());
+        let mut buf = Vec::new();
+        assert_eq!(size, buf.write_varint(c).unwrap());
+        assert_eq!(c, ReadVarint::<char>::read_varint(&mut &buf[..]).unwrap());
+    }
+
+    for invalid in [0xd800u32, 0xdfff, 0x11_0000, u32::MAX] {
+        let mut buf = Vec::new();
+        buf.write_varint(invalid).unwrap();
+        assert_eq!(
+            ErrorKind::InvalidData,
+            ReadVarint::<char>::read_varint(&mut &buf[..])
+                .unwrap_err()
+                .kind()
+        );
+    }
+}
+
+#[test]
+fn test_float_bits() {
+    fn check<T: Copy + VarintSizeHint + std::fmt::Debug>(values: &[T], to_bits: fn(T) -> u64)
+    where
+        Vec<u8>: WriteVarint<T>,
+        for<'a> &'a [u8]: ReadVarint<T>,
+    {
+        for &v in values {
+            let mut buf = Vec::new();
+            let size = buf.write_varint(v).unwrap();
+            assert_eq!(size, buf.len());
+            assert_eq!(size, v.varint_size());
+            let decoded: T = (&buf[..]).read_varint().unwrap();
+            assert_eq!(to_bits(v), to_bits(decoded), "{:?}", v);
+        }
+    }
+
+    check(
+        &[
+            0.0f32,
+            -0.0,
+            1.5,
+            f32::MIN_POSITIVE / 2.0,
+            f32::from_bits(1),
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::NAN,
+            f32::from_bits(0x7fc0_1234),
+            f32::from_bits(0xffc0_0001),
+        ],
+        |v| u64::from(v.to_bits()),
+    );
+    check(
+        &[
+            0.0f64,
+            -0.0,
+            1.5,
+            f64::MIN_POSITIVE / 2.0,
+            f64::from_bits(1),
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::NAN,
+            f64::from_bits(0x7ff8_0000_dead_beef),
+            f64::from_bits(0xfff8_0000_0000_0001),
+        ],
+        f64::to_bits,
+    );
+
+    assert_eq!(1, 0.0f64.varint_size());
+    assert_eq!(9, (-0.0f64).varint_size());
+    assert_eq!(5, 1.0f32.varint_size());
+}
+
+#[test]
+fn test_bool() {
+    let mut cur = Cursor::new(Vec::new());
+    assert_eq!(1, cur.write_varint(true).unwrap());
+    cur.write_varint(300u32).unwrap();
+    assert_eq!(1, cur.write_varint(false).unwrap());
+    assert_eq!(vec![1, 241, 60, 0], *cur.get_ref());
+    cur.set_position(0);
+    assert!(ReadVarint::<bool>::read_varint(&mut cur).unwrap());
+    assert_eq!(300u32, cur.read_varint().unwrap());
+    assert!(!ReadVarint::<bool>::read_varint(&mut cur).unwrap());
+    assert_eq!(1, true.varint_size());
+
+    for invalid in [&[2u8][..], &[240], &[241, 0]] {
+        assert_eq!(
+            ErrorKind::InvalidData,
+            ReadVarint::<bool>::read_varint(&mut &invalid[..])
+                .unwrap_err()
+                .kind()
+        );
+    }
+}
+
+#[test]
+fn test_skip_varint() {
+    let mut cur = Cursor::new(Vec::new());
+    let values = [0u128, 241, 2032, 1 << 30, u128::from(u64::MAX), u128::MAX];
+    for v in values {
+        cur.write_varint(v).unwrap();
+    }
+    cur.write_varint(7u8).unwrap();
+    let buf = cur.into_inner();
+
+    let mut r = &buf[..];
+    let mut offset = 0;
+    for v in values {
+        let len = skip_varint(&mut r).unwrap();
+        assert_eq!(v.varint_size(), len);
+        assert_eq!(Ok(len), skip_varint_slice(&buf[offset..]));
+        offset += len;
+    }
+    assert_eq!(7u8, r.read_varint().unwrap());
+
+    assert_eq!(
+        ErrorKind::UnexpectedEof,
+        skip_varint(&mut &[253u8, 1, 2][..]).unwrap_err().kind()
+    );
+    assert_eq!(
+        Err(VarintError::UnexpectedEof),
+        skip_varint_slice(&[253u8, 1, 2])
+    );
+    assert_eq!(Err(VarintError::UnexpectedEof), skip_varint_slice(&[]));
+}
+
+#[test]
+fn test_read_varint_to_buf() {
+    let mut cur = Cursor::new(Vec::new());
+    let values = [0u128, 241, 2032, 1 << 30, u128::from(u64::MAX), u128::MAX];
+    for v in values {
+        cur.write_varint(v).unwrap();
+    }
+    let encoded = cur.into_inner();
+
+    let mut r = &encoded[..];
+    let mut raw = Vec::new();
+    let mut buf = [0u8; 17];
+    for v in values {
+        let (decoded, len) = read_varint_to_buf(&mut r, &mut buf).unwrap();
+        assert_eq!((v, v.varint_size()), (decoded, len));
+        raw.extend_from_slice(&buf[..len]);
+    }
+    assert_eq!(encoded, raw);
+
+    assert_eq!(
+        ErrorKind::UnexpectedEof,
+        read_varint_to_buf(&mut &[253u8, 1, 2][..], &mut buf)
+            .unwrap_err()
+            .kind()
+    );
+    assert_eq!(
+        ErrorKind::UnexpectedEof,
+        read_varint_to_buf(&mut &[][..], &mut buf)
+            .unwrap_err()
+            .kind()
+    );
+}
+
+#[test]
+fn test_read_varint_limited() {
+    let mut buf = Vec::new();
+    buf.write_varint(u64::from(u32::MAX)).unwrap();
+    buf.write_varint(7u64).unwrap();
+
+    let mut r = &buf[..];
+    let err = ReadVarint::<u64>::read_varint_limited(&mut r, 4).unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, err.kind());
+    assert_eq!(
+        Some(&VarintError::InvalidLeadingByte(250)),
+        err.get_ref().and_then(|e| e.downcast_ref())
+    );
+    // only the leading byte is consumed
+    assert_eq!(buf.len() - 1, r.len());
+
+    let mut r = &buf[..];
+    assert_eq!(
+        u64::from(u32::MAX),
+        ReadVarint::<u64>::read_varint_limited(&mut r, 5).unwrap()
+    );
+    assert_eq!(
+        7,
+        ReadVarint::<u64>::read_varint_limited(&mut r, 1).unwrap()
+    );
+
+    // the type's own limit still applies
+    let err = ReadVarint::<u8>::read_varint_limited(&mut &[248u8, 0, 0][..], 17).unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, err.kind());
+    assert_eq!(
+        Some(&VarintError::Overflow),
+        err.get_ref().and_then(|e| e.downcast_ref())
+    );
+}
+
+#[test]
+fn test_cmp_encoded() {
+    use std::cmp::Ordering;
+
+    let values = [
+        0u128,
+        239,
+        240,
+        241,
+        2031,
+        2032,
+        67823,
+        67824,
+        1 << 24,
+        u128::from(u64::MAX),
+        u128::MAX,
+    ];
+    let encoded: Vec<Vec<u8>> = values
+        .iter()
+        .map(|v| {
+            let mut buf = Vec::new();
+            buf.write_varint(*v).unwrap();
+            buf
+        })
+        .collect();
+    for (a, ea) in values.iter().zip(&encoded) {
+        for (b, eb) in values.iter().zip(&encoded) {
+            assert_eq!(Some(a.cmp(b)), cmp_encoded(ea, eb));
+        }
+    }
+
+    let mut padded = Vec::new();
+    padded.write_varint_min_width(5u64, 9).unwrap();
+    assert_eq!(Some(Ordering::Equal), cmp_encoded(&padded, &[5]));
+    assert_eq!(Some(Ordering::Less), cmp_encoded(&padded, &[241, 0]));
+
+    // non-canonical 2-byte form of 240
+    assert_eq!(Some(Ordering::Equal), cmp_encoded(&[240], &[241, 0]));
+    assert_eq!(Some(Ordering::Equal), cmp_encoded(&[241, 0], &[240]));
+    assert_eq!(Some(Ordering::Less), cmp_encoded(&[239], &[241, 0]));
+    assert_eq!(Some(Ordering::Greater), cmp_encoded(&[241, 1], &[240]));
+
+    assert_eq!(None, cmp_encoded(&[], &[0]));
+    assert_eq!(None, cmp_encoded(&[0, 0], &[0]));
+    assert_eq!(None, cmp_encoded(&[0], &[250, 0]));
+}
+
+#[test]
+fn test_codec_metadata() {
+    fn meta<T: VarintBaseType>() -> (bool, u32, usize) {
+        (T::SIGNED, T::BITS, T::MAX_ENCODED_LEN)
+    }
+
+    assert_eq!((false, 8, 2), meta::<u8>());
+    assert_eq!((false, 16, 3), meta::<u16>());
+    assert_eq!((false, 32, 5), meta::<u32>());
+    assert_eq!((false, 64, 9), meta::<u64>());
+    assert_eq!((false, 128, 17), meta::<u128>());
+    assert_eq!((true, 8, 2), meta::<i8>());
+    assert_eq!((true, 16, 3), meta::<i16>());
+    assert_eq!((true, 32, 5), meta::<i32>());
+    assert_eq!((true, 64, 9), meta::<i64>());
+    assert_eq!((true, 128, 17), meta::<i128>());
+    assert_eq!((false, usize::BITS, 9), meta::<usize>());
+    assert_eq!((true, isize::BITS, 9), meta::<isize>());
+}
+
+fn check_leading_bytes<T: VarintCodec>()
+where
+    for<'a> &'a [u8]: ReadVarint<T>,
+{
+    for payload in [0u8, 0xff] {
+        for b in 0..=255u8 {
+            let mut buf = [payload; 17];
+            buf[0] = b;
+            let len = varint_len_from_first_byte(b);
+            let (wide, _) = u128::decode_from_slice(&buf).unwrap();
+            let fits = len <= T::MAX_ENCODED_LEN && (T::BITS == 128 || wide >> T::BITS == 0);
+            let expected = if fits {
+                Ok(len)
+            } else {
+                Err(VarintError::Overflow)
+            };
+            let ctx = (T::BITS, T::SIGNED, b, payload);
+
+            assert_eq!(
+                expected,
+                T::decode_from_slice(&buf).map(|(_, len)| len),
+                "{:?}",
+                ctx
+            );
+            let mut r = &buf[..];
+            assert_eq!(
+                expected,
+                ReadVarint::<T>::try_read_varint(&mut r).map(|_| 17 - r.len()),
+                "{:?}",
+                ctx
+            );
+            let mut it = buf.iter().copied();
+            assert_eq!(
+                expected,
+                read_varint_iter::<T>(&mut it).map(|_| 17 - it.len()),
+                "{:?}",
+                ctx
+            );
+            match ReadVarint::<T>::read_varint(&mut &buf[..]) {
+                Ok(_) => assert!(fits, "{:?}", ctx),
+                Err(e) => {
+                    assert!(!fits, "{:?}", ctx);
+                    assert_eq!(ErrorKind::InvalidData, e.kind());
+                    assert_eq!(
+                        Some(&VarintError::Overflow),
+                        e.get_ref().and_then(|e| e.downcast_ref()),
+                        "{:?}",
+                        ctx
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_narrowing_errors() {
+    check_leading_bytes::<u8>();
+    check_leading_bytes::<u16>();
+    check_leading_bytes::<u32>();
+    check_leading_bytes::<u64>();
+    check_leading_bytes::<u128>();
+    check_leading_bytes::<i8>();
+    check_leading_bytes::<i16>();
+    check_leading_bytes::<i32>();
+    check_leading_bytes::<i64>();
+    check_leading_bytes::<i128>();
+    check_leading_bytes::<usize>();
+    check_leading_bytes::<isize>();
+}
+
+/// `BufRead` over data arriving in chunks, keeping unconsumed bytes across refills
+struct Incoming {
+    chunks: Vec<Vec<u8>>,
+    buf: Vec<u8>,
+}
+
+impl Incoming {
+    fn receive(&mut self) {
+        let chunk = self.chunks.remove(0);
+        self.buf.extend_from_slice(&chunk);
+    }
+}
+
+impl std::io::Read for Incoming {
+    fn read(&mut self, out: &mut [u8]) -> std::io::Result<usize> {
+        let n = std::io::Read::read(&mut &self.buf[..], out)?;
+        self.buf.drain(..n);
+        Ok(n)
+    }
+}
+
+impl std::io::BufRead for Incoming {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        Ok(&self.buf)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.buf.drain(..amt);
+    }
+}
+
+#[test]
+fn test_try_read_varint_bufread() {
+    let mut encoded = Vec::new();
+    for v in [5u64, 2032, u64::MAX] {
+        encoded.write_varint(v).unwrap();
+    }
+    let mut r = Incoming {
+        chunks: vec![
+            encoded[..2].to_vec(),
+            encoded[2..4].to_vec(),
+            encoded[4..].to_vec(),
+        ],
+        buf: Vec::new(),
+    };
+
+    assert_eq!(None, try_read_varint_bufread::<u64, _>(&mut r).unwrap());
+    r.receive();
+    assert_eq!(Some(5), try_read_varint_bufread::<u64, _>(&mut r).unwrap());
+    assert_eq!(None, try_read_varint_bufread::<u64, _>(&mut r).unwrap());
+    assert_eq!(1, r.buf.len());
+    r.receive();
+    assert_eq!(
+        Some(2032),
+        try_read_varint_bufread::<u64, _>(&mut r).unwrap()
+    );
+    assert_eq!(None, try_read_varint_bufread::<u64, _>(&mut r).unwrap());
+    r.receive();
+    assert_eq!(
+        Some(u64::MAX),
+        try_read_varint_bufread::<u64, _>(&mut r).unwrap()
+    );
+    assert!(r.buf.is_empty());
+
+    let mut r = &[249u8][..];
+    assert_eq!(
+        ErrorKind::InvalidData,
+        try_read_varint_bufread::<u16, _>(&mut r)
+            .unwrap_err()
+            .kind()
+    );
+    assert_eq!(1, r.len());
+}
+
+fn check_strict<T: VarintCodec + std::fmt::Debug>(max_leading_byte: u8)
+where
+    for<'a> &'a [u8]: ReadVarint<T>,
+{
+    assert_eq!(max_leading_byte, T::MAX_LEADING_BYTE);
+    for b in 0..=255u8 {
+        let mut buf = [0u8; 17];
+        buf[0] = b;
+        let len = varint_len_from_first_byte(b);
+        let mut r = &buf[..];
+        if b <= max_leading_byte {
+            assert_eq!(
+                Ok(len),
+                read_varint_slice_strict::<T>(&buf).map(|(_, len)| len)
+            );
+            ReadVarint::<T>::read_varint_strict(&mut r).unwrap();
+            assert_eq!(17 - len, r.len());
+        } else {
+            assert_eq!(
+                Err(VarintError::InvalidLeadingByte(b)),
+                read_varint_slice_strict::<T>(&buf).map(|(_, len)| len)
+            );
+            let err = ReadVarint::<T>::read_varint_strict(&mut r).unwrap_err();
+            assert_eq!(ErrorKind::InvalidData, err.kind());
+            assert_eq!(
+                Some(&VarintError::InvalidLeadingByte(b)),
+                err.get_ref().and_then(|e| e.downcast_ref())
+            );
+            assert_eq!(16, r.len());
+        }
+    }
+}
+
+#[test]
+fn test_strict_leading_bytes() {
+    check_strict::<u8>(241);
+    check_strict::<i8>(241);
+    check_strict::<u16>(248);
+    check_strict::<i16>(248);
+    check_strict::<u32>(250);
+    check_strict::<i32>(250);
+    check_strict::<u64>(254);
+    check_strict::<i64>(254);
+    check_strict::<u128>(255);
+    check_strict::<i128>(255);
+
+    // the largest value of each width uses the largest accepted leading byte
+    assert_eq!(241, Varint(u8::MAX).encode()[0]);
+    assert_eq!(248, Varint(u16::MAX).encode()[0]);
+    assert_eq!(250, Varint(u32::MAX).encode()[0]);
+    assert_eq!(254, Varint(u64::MAX).encode()[0]);
+
+    // the payload is still decoded as usual
+    assert_eq!(
+        Err(VarintError::Overflow),
+        read_varint_slice_strict::<u8>(&[241, 16])
+    );
+    assert_eq!(
+        Err(VarintError::UnexpectedEof),
+        read_varint_slice_strict::<u8>(&[])
+    );
+}
+
+#[test]
+fn test_duration_and_system_time() {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    for d in [
+        Duration::ZERO,
+        Duration::from_millis(1500),
+        Duration::new(u64::MAX, 999_999_999),
+    ] {
+        let mut buf = Vec::new();
+        let size = buf.write_varint(d).unwrap();
+        assert_eq!(d.varint_size(), size);
+        assert_eq!(size, buf.len());
+        let mut r = &buf[..];
+        assert_eq!(d.as_secs(), ReadVarint::<u64>::read_varint(&mut r).unwrap());
+        assert_eq!(
+            d.subsec_nanos(),
+            ReadVarint::<u32>::read_varint(&mut r).unwrap()
+        );
+        assert_eq!(d, (&buf[..]).read_varint().unwrap());
+    }
+
+    let mut buf = Vec::new();
+    buf.write_varint(1u64).unwrap();
+    buf.write_varint(1_000_000_000u32).unwrap();
+    assert_eq!(
+        ErrorKind::InvalidData,
+        ReadVarint::<Duration>::read_varint(&mut &buf[..])
+            .unwrap_err()
+            .kind()
+    );
+
+    let now = SystemTime::now();
+    let mut buf = Vec::new();
+    buf.write_varint(now).unwrap();
+    assert_eq!(now, (&buf[..]).read_varint().unwrap());
+    let mut expected = Vec::new();
+    expected
+        .write_varint(now.duration_since(UNIX_EPOCH).unwrap())
+        .unwrap();
+    assert_eq!(expected, buf);
+
+    let before_epoch = UNIX_EPOCH - Duration::from_secs(1);
+    assert_eq!(
+        ErrorKind::InvalidInput,
+        Vec::new().write_varint(before_epoch).unwrap_err().kind()
+    );
 }
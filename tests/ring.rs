@@ -0,0 +1,30 @@
+use varuint::{ReadVarint, RingReader, RingWriter, WriteVarint};
+
+#[test]
+fn test_split_at_wrap() {
+    for split in 0..5 {
+        let mut buf = [0u8; 8];
+        let start = 8 - split;
+        let mut w = RingWriter::new(&mut buf, start);
+        assert_eq!(5, w.write_varint(u32::MAX).unwrap());
+        assert_eq!((start + 5) % 8, w.head());
+
+        let mut r = RingReader::new(&buf, start, 5);
+        assert_eq!(u32::MAX, r.read_varint().unwrap());
+        assert_eq!(0, r.remaining());
+        assert!(ReadVarint::<u8>::read_varint(&mut r).is_err());
+    }
+}
+
+#[test]
+fn test_overwrite() {
+    let mut buf = [0u8; 4];
+    let mut w = RingWriter::new(&mut buf, 0);
+    for v in 0..10u8 {
+        w.write_varint(v).unwrap();
+    }
+    assert_eq!(2, w.head());
+    let mut r = RingReader::new(&buf, 2, 4);
+    let values: Vec<u8> = (0..4).map(|_| r.read_varint().unwrap()).collect();
+    assert_eq!(vec![6, 7, 8, 9], values);
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "serde-support")]
+
+use varuint::{Varint, Varuint, WriteVarint};
+
+#[test]
+fn test_bincode() {
+    for v in [0i64, -1, 300, -70_000, i64::MIN, i64::MAX] {
+        let encoded = bincode::serialize(&Varint(v)).unwrap();
+        let mut expected = Vec::new();
+        let len = expected.write_varint(v).unwrap();
+        // bincode prefixes byte strings with a u64 length
+        assert_eq!(
+            len as u64,
+            u64::from_le_bytes(encoded[..8].try_into().unwrap())
+        );
+        assert_eq!(expected[..], encoded[8..]);
+        assert_eq!(Varint(v), bincode::deserialize(&encoded).unwrap());
+    }
+
+    let encoded = bincode::serialize(&Varuint(u128::MAX)).unwrap();
+    assert_eq!(8 + 17, encoded.len());
+    assert_eq!(Varuint(u128::MAX), bincode::deserialize(&encoded).unwrap());
+
+    // a u32 can't hold the 9-byte form
+    let encoded = bincode::serialize(&Varint(u64::MAX)).unwrap();
+    assert!(bincode::deserialize::<Varint<u32>>(&encoded).is_err());
+    // trailing bytes after the value
+    let encoded = bincode::serialize(&[5u8, 0][..]).unwrap();
+    assert!(bincode::deserialize::<Varint<u32>>(&encoded).is_err());
+}
+
+#[test]
+fn test_json() {
+    assert_eq!("-300", serde_json::to_string(&Varint(-300i32)).unwrap());
+    assert_eq!("300", serde_json::to_string(&Varuint(300u16)).unwrap());
+    assert_eq!(Varint(-300i32), serde_json::from_str("-300").unwrap());
+    assert_eq!(
+        vec![Varuint(1u64), Varuint(u64::MAX)],
+        serde_json::from_str::<Vec<Varuint<u64>>>("[1, 18446744073709551615]").unwrap()
+    );
+}
@@ -0,0 +1,59 @@
+use std::io::ErrorKind;
+use varuint::{read_string, write_string, Varint, Varuint};
+
+#[test]
+fn test_from_str() {
+    assert_eq!(Varint(-56_782i128), "-56782".parse().unwrap());
+    assert_eq!(Varint(255u8), "255".parse().unwrap());
+    assert!("256".parse::<Varint<u8>>().is_err());
+    assert!("x".parse::<Varint<i32>>().is_err());
+    assert_eq!(Varuint(u128::MAX), u128::MAX.to_string().parse().unwrap());
+    assert!("-1".parse::<Varuint<u64>>().is_err());
+}
+
+#[test]
+fn test_string_prefixed() {
+    let long = "ü".repeat(200);
+    let mut buf = Vec::new();
+    assert_eq!(1, write_string(&mut buf, "").unwrap());
+    assert_eq!(7, write_string(&mut buf, "héllo").unwrap());
+    assert_eq!(402, write_string(&mut buf, &long).unwrap());
+    assert_eq!([6, b'h', 0xc3, 0xa9], buf[1..5]);
+
+    let mut r = &buf[..];
+    assert_eq!("", read_string(&mut r, 0).unwrap());
+    assert_eq!("héllo", read_string(&mut r, 6).unwrap());
+    assert_eq!(long, read_string(&mut r, 400).unwrap());
+    assert!(r.is_empty());
+
+    assert_eq!(
+        ErrorKind::InvalidData,
+        read_string(&mut &buf[1..], 5).unwrap_err().kind()
+    );
+    assert_eq!(
+        ErrorKind::InvalidData,
+        read_string(&mut &[2u8, 0xc3, 0x28][..], 2)
+            .unwrap_err()
+            .kind()
+    );
+    assert_eq!(
+        ErrorKind::UnexpectedEof,
+        read_string(&mut &buf[1..4], 6).unwrap_err().kind()
+    );
+}
+
+#[cfg(feature = "test-utils")]
+#[test]
+fn test_str_roundtrip() {
+    use varuint::check_str_roundtrip;
+
+    assert!(check_str_roundtrip(0u8));
+    assert!(check_str_roundtrip(i8::MIN));
+    assert!(check_str_roundtrip(u16::MAX));
+    assert!(check_str_roundtrip(i32::MIN));
+    assert!(check_str_roundtrip(u64::MAX));
+    assert!(check_str_roundtrip(i128::MIN));
+    assert!(check_str_roundtrip(i128::MAX));
+    assert!(check_str_roundtrip(u128::MAX));
+    assert!(check_str_roundtrip(0i128));
+}
@@ -0,0 +1,33 @@
+use varuint::{ValidatedVarint, VarintError, WriteVarint};
+
+#[test]
+fn test_validated_decode() {
+    let mut buf = Vec::new();
+    for v in [0i64, -1, 2031, i64::MIN, i64::MAX] {
+        buf.write_varint(v).unwrap();
+    }
+    let mut rest = &buf[..];
+    for v in [0i64, -1, 2031, i64::MIN, i64::MAX] {
+        let validated = ValidatedVarint::<i64>::decode(rest).unwrap();
+        assert_eq!(v, *validated);
+        assert_eq!(ValidatedVarint::from(v), validated);
+        rest = &rest[validated.byte_len()..];
+    }
+    assert!(rest.is_empty());
+
+    let mut padded = Vec::new();
+    padded.write_varint_min_width(5u32, 4).unwrap();
+    assert_eq!(
+        Err(VarintError::NonCanonical),
+        ValidatedVarint::<u32>::decode(&padded)
+    );
+    assert_eq!(
+        Err(VarintError::Overflow),
+        ValidatedVarint::<u8>::decode(&[241, 16])
+    );
+    assert_eq!(
+        Err(VarintError::UnexpectedEof),
+        ValidatedVarint::<u64>::decode(&[250, 0])
+    );
+    assert_eq!(u128::MAX, ValidatedVarint::from(u128::MAX).into_inner());
+}
@@ -1,9 +1,12 @@
-use varuint::{Deserializable, Serializable, Varint, VarintBaseType};
+use varuint::{
+    from_varint_slice, read_bool_vec, read_length_delimited, read_tagged_sign, read_varint_vec,
+    recode_canonical, to_varint_vec, varint_size_u64, write_bool_vec, write_length_delimited,
+    write_nested, write_tagged_sign, write_varint_slice_prefixed, Deserializable, Serializable,
+    Varint, VarintBaseType, VarintError, VarintLen, VarintSizeHint, Varuint, WriteVarint,
+    MAX_VARINT_LEN,
+};
 
-fn test_varuint<T: VarintBaseType>(v: T, size: usize)
-where
-    Varint<T>: Serializable + Deserializable,
-{
+fn test_varuint<T: VarintBaseType>(v: T, size: usize) {
     let v = Varint::<T>(v);
     assert_eq!(size, v.size_hint());
     let mut arr: [u8; 17] = [0u8; 17];
@@ -65,7 +68,568 @@ fn test_all() {
     test_varuint(281474976710656u128, 8);
     test_varuint(72057594037927935u128, 8);
     test_varuint(72057594037927936u128, 9);
-    test_varuint(u128::from(u64::max_value()), 9);
-    test_varuint(u128::from(u64::max_value()) + 1, 17);
-    test_varuint(u128::max_value(), 17);
+    test_varuint(u128::from(u64::MAX), 9);
+    test_varuint(u128::from(u64::MAX) + 1, 17);
+    test_varuint(u128::MAX, 17);
+
+    test_varuint(240usize, 1);
+    test_varuint(2032usize, 3);
+    test_varuint(-5isize, 1);
+    test_varuint(isize::MIN, 9);
+}
+
+fn test_composite<T: Serializable + Deserializable + VarintLen + PartialEq + std::fmt::Debug>(
+    v: T,
+) {
+    let mut buf = Vec::new();
+    let size = v.serialize(&mut buf).unwrap();
+    assert_eq!(size, buf.len());
+    assert_eq!(size, v.size_hint());
+    assert_eq!(size, v.encoded_len());
+    assert_eq!(v, T::deserialize(&mut &buf[..]).unwrap());
+}
+
+#[test]
+fn test_composites() {
+    test_composite(None::<Varint<u32>>);
+    test_composite(Some(Varint(-70_000i64)));
+    test_composite(Vec::<Varint<u8>>::new());
+    test_composite(
+        (0..300u128)
+            .map(|i| Varint(i << (i % 128)))
+            .collect::<Vec<_>>(),
+    );
+    test_composite(vec![Some(Varint(1u16)), None]);
+
+    assert_eq!(3, (5u8, 300u32).encoded_len());
+    assert_eq!(20, (Varint(-1i8), u128::MAX, Some(0u64)).encoded_len());
+    assert!(Option::<Varint<u8>>::deserialize(&mut &[2u8][..]).is_err());
+}
+
+#[test]
+fn test_write_nested() {
+    let mut buf = Vec::new();
+    let size = write_nested(&mut buf, |body| {
+        Varint(300u32).serialize(body)?;
+        write_nested(body, |inner| Varint(-1i8).serialize(inner).map(|_| ()))?;
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(size, buf.len());
+    assert_eq!(vec![4, 241, 60, 1, 1], buf);
+
+    let res = write_nested(&mut buf, |_| Err(std::io::ErrorKind::Other.into()));
+    assert!(res.is_err());
+    assert_eq!(5, buf.len());
+}
+
+#[test]
+fn test_bool_vec() {
+    let bits: Vec<bool> = (0..19).map(|i| i % 3 == 0).collect();
+    let mut buf = Vec::new();
+    assert_eq!(4, write_bool_vec(&mut buf, &bits).unwrap());
+    assert_eq!(vec![19, 0b0100_1001, 0b1001_0010, 0b0000_0100], buf);
+    assert_eq!(bits, read_bool_vec(&mut &buf[..], 19).unwrap());
+    assert!(read_bool_vec(&mut &buf[..], 18).is_err());
+
+    buf[3] |= 0xf0;
+    assert_eq!(bits, read_bool_vec(&mut &buf[..], 19).unwrap());
+
+    let mut buf = Vec::new();
+    assert_eq!(1, write_bool_vec(&mut buf, &[]).unwrap());
+    assert!(read_bool_vec(&mut &buf[..], 0).unwrap().is_empty());
+}
+
+#[test]
+fn test_length_delimited() {
+    let data: Vec<u8> = (0..=255).collect();
+    let mut buf = Vec::new();
+    assert_eq!(258, write_length_delimited(&mut buf, &data).unwrap());
+    assert_eq!(1, write_length_delimited(&mut buf, &[]).unwrap());
+    assert_eq!(vec![241, 16, 0, 1], buf[..4]);
+
+    let mut r = &buf[..];
+    assert_eq!(data, read_length_delimited(&mut r, 256).unwrap());
+    assert!(read_length_delimited(&mut r, 0).unwrap().is_empty());
+    assert!(r.is_empty());
+
+    assert_eq!(
+        std::io::ErrorKind::InvalidData,
+        read_length_delimited(&mut &buf[..], 255)
+            .unwrap_err()
+            .kind()
+    );
+    assert_eq!(
+        std::io::ErrorKind::UnexpectedEof,
+        read_length_delimited(&mut &buf[..100], 256)
+            .unwrap_err()
+            .kind()
+    );
+}
+
+#[test]
+fn test_encoded_len() {
+    assert_eq!(1, Varint(240u8).encoded_len());
+    assert!(Varint(240u8).is_single_byte());
+    assert!(!Varint(241u64).is_single_byte());
+    assert!(Varint(-120i32).is_single_byte());
+    assert_eq!(17, Varint(i128::MIN).encoded_len());
+}
+
+#[test]
+fn test_fits_in() {
+    assert!(Varint(0u8).fits_in(1));
+    assert!(!Varint(0u8).fits_in(0));
+    assert!(Varint(2031u32).fits_in(2));
+    assert!(!Varint(2032u32).fits_in(2));
+    assert!(Varint(i128::MIN).fits_in(17));
+    assert!(!Varint(i128::MIN).fits_in(16));
+
+    const SIZES: [bool; 2] = [varint_size_u64(67_567) <= 3, varint_size_u64(67_568) <= 3];
+    assert_eq!([true, false], SIZES);
+}
+
+#[test]
+fn test_tagged_sign() {
+    let mut buf = Vec::new();
+    assert_eq!(2, write_tagged_sign(&mut buf, -1, true).unwrap());
+    assert_eq!(2, write_tagged_sign(&mut buf, 200, false).unwrap());
+    assert_eq!(18, write_tagged_sign(&mut buf, i128::MAX, false).unwrap());
+    assert!(write_tagged_sign(&mut buf, -1, false).is_err());
+    assert_eq!(vec![1, 1, 0, 200], buf[..4]);
+
+    let mut r = &buf[..];
+    assert_eq!((-1, true), read_tagged_sign(&mut r).unwrap());
+    assert_eq!((200, false), read_tagged_sign(&mut r).unwrap());
+    assert_eq!((i128::MAX, false), read_tagged_sign(&mut r).unwrap());
+    assert!(r.is_empty());
+
+    assert!(read_tagged_sign(&mut &[2u8, 0][..]).is_err());
+    let mut buf = vec![0u8];
+    Varint(u128::MAX).serialize(&mut buf).unwrap();
+    assert!(read_tagged_sign(&mut &buf[..]).is_err());
+}
+
+#[test]
+fn test_to_rust_literal() {
+    assert_eq!("[0x05]", Varint(5u8).to_rust_literal());
+    assert_eq!("[0xf1, 0x01]", Varint(241u16).to_rust_literal());
+    assert_eq!(
+        "[0xfa, 0x00, 0x00, 0x00, 0x01]",
+        Varint(1u32 << 24).to_rust_literal()
+    );
+}
+
+fn test_slice_codec<T: VarintBaseType>(v: T) {
+    let v = Varint(v);
+    let mut expected = Vec::new();
+    v.serialize(&mut expected).unwrap();
+    let mut arr = [0u8; 17];
+    assert_eq!(expected.len(), v.encode_into(&mut arr));
+    assert_eq!(expected[..], arr[..expected.len()]);
+    assert_eq!(Ok((v.0, expected.len())), Varint::<T>::decode_from(&arr));
+}
+
+#[test]
+fn test_encode_into_decode_from() {
+    for v in [
+        0u128,
+        240,
+        241,
+        2031,
+        2032,
+        67567,
+        67568,
+        1 << 40,
+        u64::MAX.into(),
+        u128::MAX,
+    ] {
+        test_slice_codec(v);
+        test_slice_codec(v as u64);
+        test_slice_codec(v as u16);
+        test_slice_codec(v as i128);
+        test_slice_codec(v as i32);
+        test_slice_codec(v as i8);
+        test_slice_codec(v as u64 as usize);
+        test_slice_codec(v as i64 as isize);
+    }
+
+    assert_eq!(
+        Err(VarintError::UnexpectedEof),
+        Varint::<u32>::decode_from(&[])
+    );
+    assert_eq!(
+        Err(VarintError::UnexpectedEof),
+        Varint::<u32>::decode_from(&[249, 0])
+    );
+    assert_eq!(
+        Err(VarintError::Overflow),
+        Varint::<u8>::decode_from(&[248, 0, 0])
+    );
+    assert_eq!(
+        Err(VarintError::Overflow),
+        Varint::<u8>::decode_from(&[241, 16])
+    );
+}
+
+#[test]
+fn test_varuint_wrapper() {
+    for v in [0u64, 240, 241, 2032, 67568, u64::MAX] {
+        let mut buf = Vec::new();
+        let size = Varuint(v).serialize(&mut buf).unwrap();
+        assert_eq!(size, Varuint(v).size_hint());
+        assert_eq!(size, Varuint(v).encoded_len());
+        assert_eq!(Varuint(v), Varuint::deserialize(&mut &buf[..]).unwrap());
+
+        let mut expected = Vec::new();
+        Varint(v).serialize(&mut expected).unwrap();
+        assert_eq!(expected, buf);
+    }
+
+    let mut buf = Vec::new();
+    Varuint(2032usize).serialize(&mut buf).unwrap();
+    assert_eq!(
+        Varuint(2032usize),
+        Varuint::deserialize(&mut &buf[..]).unwrap()
+    );
+
+    assert_eq!(Varuint(300u32), Varuint::from(300u16));
+    assert_eq!(Varuint(0u8), Varuint::default());
+    assert_eq!("77", Varuint(77u16).to_string());
+    assert_eq!(Varuint(77u16), "77".parse().unwrap());
+    let mut v = Varuint(1u64);
+    *v += 1;
+    assert_eq!(2, *v);
+}
+
+#[test]
+fn test_max_encoded_len() {
+    let buf = [0u8; Varint::<u64>::MAX_ENCODED_LEN];
+    assert_eq!(9, buf.len());
+    assert_eq!(MAX_VARINT_LEN, Varint::<u128>::MAX_ENCODED_LEN);
+    assert_eq!(2, Varint::<u8>::MAX_ENCODED_LEN);
+    assert_eq!(3, Varuint::<u16>::MAX_ENCODED_LEN);
+
+    assert_eq!(Varint::<u8>::MAX_ENCODED_LEN, u8::MAX.varint_size());
+    assert_eq!(Varint::<i8>::MAX_ENCODED_LEN, i8::MIN.varint_size());
+    assert_eq!(Varint::<u16>::MAX_ENCODED_LEN, u16::MAX.varint_size());
+    assert_eq!(Varint::<i16>::MAX_ENCODED_LEN, i16::MIN.varint_size());
+    assert_eq!(Varint::<u32>::MAX_ENCODED_LEN, u32::MAX.varint_size());
+    assert_eq!(Varint::<i32>::MAX_ENCODED_LEN, i32::MIN.varint_size());
+    assert_eq!(Varint::<u64>::MAX_ENCODED_LEN, u64::MAX.varint_size());
+    assert_eq!(Varint::<i64>::MAX_ENCODED_LEN, i64::MIN.varint_size());
+    assert_eq!(Varint::<u128>::MAX_ENCODED_LEN, u128::MAX.varint_size());
+    assert_eq!(Varint::<i128>::MAX_ENCODED_LEN, i128::MIN.varint_size());
+}
+
+#[test]
+fn test_varint_slice_prefixed() {
+    let values = [0u32, 240, 241, 67568, u32::MAX];
+    let mut buf = Vec::new();
+    let size = write_varint_slice_prefixed(&mut buf, &values).unwrap();
+    assert_eq!(size, buf.len());
+    assert_eq!(5, buf[0]);
+    assert_eq!(
+        values[..],
+        read_varint_vec::<u32>(&mut &buf[..]).unwrap()[..]
+    );
+
+    let mut buf = Vec::new();
+    write_varint_slice_prefixed::<i8>(&mut buf, &[]).unwrap();
+    assert!(read_varint_vec::<i8>(&mut &buf[..]).unwrap().is_empty());
+
+    // forged count with no data fails on the missing elements
+    let mut buf = Vec::new();
+    buf.write_varint(u64::MAX).unwrap();
+    assert!(read_varint_vec::<u8>(&mut &buf[..]).is_err());
+}
+
+#[test]
+fn test_arithmetic() {
+    assert_eq!(Varint(7u64), Varint(3u64) + Varint(4));
+    assert_eq!(Varint(-1i32), Varint(3i32) - Varint(4));
+
+    let mut acc = Varint(100i64);
+    for delta in [Varint(5), Varint(-20), Varint(1)] {
+        acc += delta;
+    }
+    assert_eq!(Varint(86), acc);
+    acc -= Varint(86);
+    assert_eq!(Varint::default(), acc);
+}
+
+#[test]
+fn test_wrapping_saturating() {
+    use std::num::{Saturating, Wrapping};
+
+    let mut acc = Wrapping::from(Varint(u64::MAX));
+    acc += Wrapping(2);
+    assert_eq!(Varint(1u64), Varint::from(acc));
+
+    let mut acc = Saturating::from(Varint(250u8));
+    acc += Saturating(10);
+    assert_eq!(Varint(u8::MAX), Varint::from(acc));
+
+    let acc = Saturating::from(Varint(i32::MIN)) - Saturating(1);
+    assert_eq!(Varint(i32::MIN), acc.into());
+}
+
+#[test]
+fn test_sum_product() {
+    let decoded = vec![Varint(3u64), Varint(300), Varint(70_000)];
+    assert_eq!(Varint(70_303u64), decoded.iter().sum());
+    assert_eq!(Varint(63_000_000u64), decoded.into_iter().product());
+    assert_eq!(Varint(0i32), Vec::<Varint<i32>>::new().into_iter().sum());
+    assert_eq!(
+        Varint(1i32),
+        Vec::<Varint<i32>>::new().into_iter().product()
+    );
+    assert_eq!(
+        Varint(-6i8),
+        [Varint(-1i8), Varint(2), Varint(3)].iter().product()
+    );
+}
+
+#[test]
+fn test_try_from_narrowing() {
+    use std::convert::TryFrom;
+
+    assert_eq!(Ok(Varint(300u32)), Varint::<u32>::try_from(Varint(300u128)));
+    assert!(Varint::<u32>::try_from(Varint(u128::from(u32::MAX) + 1)).is_err());
+    assert_eq!(Ok(Varint(-128i8)), Varint::<i8>::try_from(Varint(-128i64)));
+    assert!(Varint::<i8>::try_from(Varint(-129i16)).is_err());
+    let v: Result<Varint<u16>, _> = Varint(70_000u64).try_into();
+    assert!(v.is_err());
+}
+
+#[test]
+fn test_vec_helpers() {
+    assert_eq!(vec![0], to_varint_vec(0u8));
+    assert_eq!(vec![248, 0, 0], to_varint_vec(2032u32));
+    assert_eq!(Varint(-56_782i128).to_vec(), to_varint_vec(-56_782i128));
+    assert_eq!(Ok(u128::MAX), from_varint_slice(&to_varint_vec(u128::MAX)));
+    assert_eq!(
+        Err(VarintError::TrailingBytes),
+        from_varint_slice::<u64>(&[5, 0])
+    );
+    assert_eq!(
+        Err(VarintError::UnexpectedEof),
+        from_varint_slice::<u64>(&[241])
+    );
+    assert_eq!(
+        Err(VarintError::UnexpectedEof),
+        from_varint_slice::<u64>(&[])
+    );
+}
+
+#[test]
+fn test_recode_canonical() {
+    let values = [
+        0u128,
+        240,
+        241,
+        2032,
+        67568,
+        1 << 40,
+        u128::from(u64::MAX) + 1,
+    ];
+    let mut canonical = Vec::new();
+    let mut padded = Vec::new();
+    for v in values {
+        canonical.write_varint(v).unwrap();
+        let width = if v >> 64 == 0 { 9 } else { 17 };
+        padded.write_varint_min_width(v, width).unwrap();
+    }
+
+    let mut dst = vec![7];
+    assert_eq!(
+        Ok(padded.len() - canonical.len()),
+        recode_canonical(&padded, &mut dst)
+    );
+    assert_eq!(canonical, dst[1..]);
+
+    let mut dst = Vec::new();
+    assert_eq!(Ok(0), recode_canonical(&canonical, &mut dst));
+    assert_eq!(canonical, dst);
+    assert_eq!(Ok(0), recode_canonical(&[], &mut dst));
+
+    let mut dst = Vec::new();
+    assert_eq!(
+        Err(VarintError::UnexpectedEof),
+        recode_canonical(&[254, 1, 0, 0, 0, 0, 0, 0, 0, 249, 0], &mut dst)
+    );
+    assert_eq!(vec![1], dst);
+}
+
+struct OneBytePerCall(Vec<u8>);
+
+impl std::io::Write for OneBytePerCall {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match buf.first() {
+            Some(&b) => {
+                self.0.push(b);
+                Ok(1)
+            }
+            None => Ok(0),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_serialize_short_writes() {
+    let mut w = OneBytePerCall(Vec::new());
+    assert_eq!(17, Varint(u128::MAX).serialize(&mut w).unwrap());
+    assert_eq!(17, Varint(i128::MIN).serialize(&mut w).unwrap());
+    assert_eq!(3, Varuint(67567u32).serialize(&mut w).unwrap());
+    assert_eq!(9, w.write_varint_min_width(1u64, 9).unwrap());
+    assert_eq!(4, write_length_delimited(&mut w, &[1, 2, 3]).unwrap());
+
+    let mut expected = Vec::new();
+    Varint(u128::MAX).serialize(&mut expected).unwrap();
+    Varint(i128::MIN).serialize(&mut expected).unwrap();
+    Varuint(67567u32).serialize(&mut expected).unwrap();
+    expected.write_varint_min_width(1u64, 9).unwrap();
+    write_length_delimited(&mut expected, &[1, 2, 3]).unwrap();
+    assert_eq!(expected, w.0);
+
+    // a full buffer fails instead of silently truncating the value
+    let mut full = [0u8; 4];
+    let err = Varint(u64::MAX).serialize(&mut &mut full[..]).unwrap_err();
+    assert_eq!(std::io::ErrorKind::WriteZero, err.kind());
+}
+
+#[test]
+fn test_hash_across_widths() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+    use std::hash::{Hash, Hasher};
+
+    fn hash_of<T: Hash>(v: T) -> u64 {
+        let mut h = DefaultHasher::new();
+        v.hash(&mut h);
+        h.finish()
+    }
+
+    for v in [0u8, 5, 240, 241, 255] {
+        let h = hash_of(Varint(v));
+        assert_eq!(h, hash_of(Varint(u16::from(v))));
+        assert_eq!(h, hash_of(Varint(u32::from(v))));
+        assert_eq!(h, hash_of(Varint(u64::from(v))));
+        assert_eq!(h, hash_of(Varint(u128::from(v))));
+        assert_eq!(h, hash_of(Varuint(u64::from(v))));
+    }
+    assert_eq!(hash_of(Varint(-3i8)), hash_of(Varint(-3i64)));
+    assert_ne!(hash_of(Varint(1u32)), hash_of(Varint(2u32)));
+
+    let set: HashSet<Varint<u64>> = [1, 300, 1, 70_000, 300].into_iter().map(Varint).collect();
+    assert_eq!(3, set.len());
+    assert!(set.contains(&Varint(70_000)));
+}
+
+#[test]
+fn test_tuples_and_arrays() {
+    let record = (Varint(300u32), Varint(-1i64), Some(Varuint(7u8)));
+    let mut buf = Vec::new();
+    assert_eq!(5, record.size_hint());
+    assert_eq!(5, record.serialize(&mut buf).unwrap());
+    assert_eq!(vec![241, 60, 1, 1, 7], buf);
+    assert_eq!(record, Deserializable::deserialize(&mut &buf[..]).unwrap());
+
+    let pair = (Varint(1u8), Varint(2u8));
+    let quad = (Varint(1u8), Varint(2u16), Varint(3u32), Varint(4u64));
+    let mut buf = Vec::new();
+    pair.serialize(&mut buf).unwrap();
+    quad.serialize(&mut buf).unwrap();
+    let mut r = &buf[..];
+    assert_eq!(pair, Deserializable::deserialize(&mut r).unwrap());
+    assert_eq!(quad, Deserializable::deserialize(&mut r).unwrap());
+
+    let values = [Varint(0u64), Varint(2032), Varint(u64::MAX)];
+    let mut buf = Vec::new();
+    assert_eq!(1 + 3 + 9, values.size_hint());
+    assert_eq!(13, values.serialize(&mut buf).unwrap());
+    assert_eq!(
+        values,
+        <[Varint<u64>; 3]>::deserialize(&mut &buf[..]).unwrap()
+    );
+    assert!(<[Varint<u64>; 0]>::deserialize(&mut &[][..]).is_ok());
+
+    assert_eq!(
+        std::io::ErrorKind::UnexpectedEof,
+        <[Varint<u64>; 4]>::deserialize(&mut &buf[..])
+            .unwrap_err()
+            .kind()
+    );
+    assert_eq!(
+        std::io::ErrorKind::InvalidData,
+        <[Varint<u8>; 2]>::deserialize(&mut &[1u8, 248, 0, 0][..])
+            .unwrap_err()
+            .kind()
+    );
+}
+
+#[test]
+fn test_encode_inline() {
+    for (v, size) in [(0u128, 1), (241, 2), (67568, 4), (u128::MAX, 17)] {
+        let bytes = Varint(v).encode();
+        assert_eq!(size, bytes.len());
+        assert_eq!(to_varint_vec(v), bytes.as_ref());
+        assert_eq!(bytes, Varuint(v).encode());
+    }
+
+    let bytes = Varint(-1i32).encode();
+    let copy = bytes;
+    assert_eq!(copy, [1]);
+    assert_eq!(*copy, [1u8][..]);
+    assert_ne!(Varint(1u8).encode(), Varint(2u8).encode());
+    assert_eq!("[241, 1]", format!("{:?}", Varint(241u64).encode()));
+
+    let mut out = Vec::new();
+    std::io::Write::write_all(&mut out, &Varint(2032u16).encode()).unwrap();
+    assert_eq!(vec![248, 0, 0], out);
+}
+
+#[test]
+fn test_reader() {
+    use std::io::Read;
+
+    let mut r = Varint(u128::MAX).reader();
+    let mut head = [0u8; 5];
+    r.read_exact(&mut head).unwrap();
+    let mut rest = Vec::new();
+    assert_eq!(12, r.read_to_end(&mut rest).unwrap());
+    assert_eq!(0, r.read(&mut head).unwrap());
+    assert_eq!(Varint(u128::MAX).to_vec(), [&head[..], &rest[..]].concat());
+
+    let v: Varint<i64> = Deserializable::deserialize(&mut Varint(-70_000i64).reader()).unwrap();
+    assert_eq!(Varint(-70_000), v);
+}
+
+#[test]
+fn test_deserialize_from_slice() {
+    let mut buf = Vec::new();
+    Varint(-300i64).serialize(&mut buf).unwrap();
+    (Varint(1u8), Some(Varuint(70_000u32)))
+        .serialize(&mut buf)
+        .unwrap();
+    buf.extend_from_slice(b"tail");
+
+    let (a, rest) = Varint::<i64>::deserialize_from_slice(&buf).unwrap();
+    assert_eq!(Varint(-300), a);
+    let (b, rest) = <(Varint<u8>, Option<Varuint<u32>>)>::deserialize_from_slice(rest).unwrap();
+    assert_eq!((Varint(1), Some(Varuint(70_000))), b);
+    assert_eq!(b"tail", rest);
+
+    let (v, rest) = Varint::<u8>::deserialize_from_slice(&[5]).unwrap();
+    assert_eq!((Varint(5), &[][..]), (v, rest));
+    assert_eq!(
+        std::io::ErrorKind::UnexpectedEof,
+        Varint::<u64>::deserialize_from_slice(&[250, 0])
+            .unwrap_err()
+            .kind()
+    );
 }